@@ -0,0 +1,33 @@
+import numpy as np
+
+alignmentSizes = {"chunk": 16, "region": 512}
+
+
+def alignedLength(length, size, mode):
+    if mode == "outward":
+        return -(-length // size) * size
+    return max(length // size, 1) * size
+
+
+def alignArea(imgarray, imgAge, alignment, mode):
+    # The area always starts at 0, 0, so only the far edges are moved
+    size = alignmentSizes[alignment]
+    sizeX, sizeZ = imgarray.shape[0], imgarray.shape[1]
+    alignedX = alignedLength(sizeX, size, mode)
+    alignedZ = alignedLength(sizeZ, size, mode)
+
+    padding = ((0, max(alignedX - sizeX, 0)), (0, max(alignedZ - sizeZ, 0)), (0, 0))
+    imgarray = np.pad(imgarray, padding)[:alignedX, :alignedZ]
+    imgAge = np.pad(imgAge, padding)[:alignedX, :alignedZ]
+
+    print(f"Aligned area from {sizeX}x{sizeZ} to {alignedX}x{alignedZ} blocks")
+    return (
+        imgarray,
+        imgAge,
+        {
+            "alignment": alignment,
+            "mode": mode,
+            "originalSize": [sizeX, sizeZ],
+            "alignedSize": [alignedX, alignedZ],
+        },
+    )
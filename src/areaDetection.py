@@ -0,0 +1,41 @@
+# Keys which describe a line unless the way is explicitly tagged with area=yes
+linearKeys = ["highway", "barrier", "railway", "waterway", "power", "route"]
+
+# Values which describe a line even though their key usually describes an area
+linearValues = {
+    "natural": ["coastline", "tree_row", "cliff", "ridge", "arete"],
+    "man_made": ["pier", "breakwater", "embankment", "groyne", "dyke"],
+    "leisure": ["track", "slipway"],
+}
+
+# Values which describe an area even though their key usually describes a line
+areaValues = {
+    "waterway": ["riverbank", "dock", "boatyard"],
+}
+
+
+def isClosed(nodes):
+    return len(nodes) >= 4 and nodes[0] == nodes[-1]
+
+
+def isArea(element):
+    if "tags" not in element or not isClosed(element["nodes"]):
+        return False
+
+    tags = element["tags"]
+    if tags.get("area") == "no":
+        return False
+    if tags.get("area") == "yes":
+        return True
+
+    for key, values in linearValues.items():
+        if key in tags and tags[key] in values:
+            return False
+    for key, values in areaValues.items():
+        if key in tags and tags[key] in values:
+            return True
+    for key in linearKeys:
+        if key in tags:
+            return False
+
+    return True
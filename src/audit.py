@@ -0,0 +1,41 @@
+import os
+import copy
+from collections import Counter
+from random import shuffle
+import numpy as np
+
+from .processData import processData
+
+
+def verifyOrderIndependence(rawdata, args, runs=3):
+    print("Processing elements in their original order...")
+    reference, _ = processData(copy.deepcopy(rawdata), args)
+
+    # Markers, teleports and rasters are written by the first run only
+    shuffledArgs = copy.copy(args)
+    shuffledArgs.markers = None
+    shuffledArgs.teleports = False
+    shuffledArgs.exportRasters = None
+
+    failed = False
+    for run in range(runs):
+        print(f"Processing elements in random order ({run + 1}/{runs})...")
+        shuffled = copy.deepcopy(rawdata)
+        shuffle(shuffled["elements"])
+        img, _ = processData(shuffled, shuffledArgs)
+
+        different = np.nonzero(img[:, :, 0] != reference[:, :, 0])
+        if len(different[0]) == 0:
+            continue
+        failed = True
+        changes = Counter(
+            (int(reference[x][y][0]), int(img[x][y][0])) for x, y in zip(*different)
+        )
+        print(f"Error! {len(different[0])} pixels depend on the processing order:")
+        for (expected, actual), count in changes.most_common(10):
+            print(f"  {count} pixels: ID {expected} became {actual}")
+
+    if failed:
+        os._exit(1)
+    print("Done! The processed data does not depend on the element order")
+    os._exit(0)
@@ -0,0 +1,68 @@
+import os
+import sys
+import json
+import tempfile
+import subprocess
+
+from .demo import demoPath
+from .progress import formatDuration
+
+repositoryPath = os.path.dirname(os.path.dirname(os.path.abspath(__file__)))
+
+
+def fixtureName(path):
+    return os.path.splitext(os.path.basename(path))[0]
+
+
+def benchCommand(fixturePath, worldPath, metricsPath):
+    # Every fixture runs in its own process, main keeps its state in globals
+    return [
+        sys.executable,
+        os.path.join(repositoryPath, "arnis.py"),
+        "--offline-demo",
+        fixturePath,
+        "--path",
+        worldPath,
+        "--new-world",
+        "--yes",
+        "--metrics-out",
+        metricsPath,
+    ]
+
+
+def formatResults(results):
+    lines = []
+    for name, report in results.items():
+        stages = report["stages"]
+        total = formatDuration(sum(stages.values()))
+        lines.append(f"{name}: {total} total")
+        for stage, duration in stages.items():
+            lines.append(f"  {stage:<12} {formatDuration(duration)}")
+    return "\n".join(lines)
+
+
+def runBench(fixtures, outputPath=None):
+    # Generates each OSM JSON fixture into a throwaway world and reports the
+    # duration of every stage, to compare the performance between versions
+    results = {}
+    for fixturePath in fixtures or [demoPath]:
+        name = fixtureName(fixturePath)
+        print(f"Benchmarking {name}...")
+        with tempfile.TemporaryDirectory(prefix="arnis-bench-") as workPath:
+            worldPath = os.path.join(workPath, "world")
+            metricsPath = os.path.join(workPath, "metrics.json")
+            subprocess.run(
+                benchCommand(os.path.abspath(fixturePath), worldPath, metricsPath),
+                cwd=workPath,
+                check=True,
+                stdout=subprocess.DEVNULL,
+            )
+            with open(metricsPath, "r", encoding="utf-8") as f:
+                results[name] = json.load(f)
+
+    print(formatResults(results))
+    if outputPath is not None:
+        with open(outputPath, "w", encoding="utf-8") as f:
+            json.dump(results, f, indent=2)
+        print(f"Wrote benchmark results to {outputPath}")
+    return results
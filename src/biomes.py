@@ -0,0 +1,62 @@
+import anvil
+from nbt import nbt
+
+# Numeric biome IDs of the chunk format written by anvil-parser, which are
+# converted to the named biomes when Minecraft upgrades the chunks
+plains = 1
+legacyBiomes = {
+    26: 25,  # Bare rock, stone shore
+    32: 4,  # Forest
+    34: 16,  # Beach
+    35: 6,  # Wetland, swamp
+    38: 7,  # Water, river
+    40: 0,  # Ocean
+}
+
+
+def columnBiome(imgarray, x, z):
+    if 0 <= x < len(imgarray) and 0 <= z < len(imgarray[0]):
+        return legacyBiomes.get(int(imgarray[x][z]), plains)
+    return plains
+
+
+def chunkBiomes(imgarray, chunkX, chunkZ):
+    # Biomes are stored per 4x4x4 cell, the same for all 64 cells of a column
+    # and sampled at the center of every cell
+    cells = [
+        columnBiome(imgarray, chunkX * 16 + cellX * 4 + 2, chunkZ * 16 + cellZ * 4 + 2)
+        for cellZ in range(4)
+        for cellX in range(4)
+    ]
+    return cells * 64
+
+
+class BiomeChunk(anvil.EmptyChunk):
+    def __init__(self, x, z, biomes):
+        super().__init__(x, z)
+        self.biomes = biomes
+
+    def save(self):
+        root = super().save()
+        biomes = nbt.TAG_Int_Array(name="Biomes")
+        biomes.value = self.biomes
+        root["Level"].tags.append(biomes)
+        return root
+
+
+def addBiomeChunks(regions, imgarray):
+    # Created before any block is set, so anvil-parser uses them for the blocks
+    for chunkX in range((len(imgarray) + 15) // 16):
+        for chunkZ in range((len(imgarray[0]) + 15) // 16):
+            x, z = chunkX * 16, chunkZ * 16
+            area = imgarray[x : x + 16, z : z + 16]
+            if (area == 1).all():  # Outside of the boundary, nothing is generated
+                continue
+            key = f"r.{chunkX // 32}.{chunkZ // 32}"
+            if key not in regions:
+                regions[key] = anvil.EmptyRegion(0, 0)
+            regions[key].add_chunk(
+                BiomeChunk(
+                    chunkX % 32, chunkZ % 32, chunkBiomes(imgarray, chunkX, chunkZ)
+                )
+            )
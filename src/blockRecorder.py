@@ -0,0 +1,40 @@
+import numpy as np
+
+recordedHeight = 40
+
+
+def blockState(block):
+    state = f"{block.namespace}:{block.id}"
+    if block.properties:
+        properties = ",".join(f"{k}={v}" for k, v in sorted(block.properties.items()))
+        state += f"[{properties}]"
+    return state
+
+
+class BlockRecorder:
+    # Keeps a copy of the generated blocks for the exports besides the regions
+    def __init__(self, width, height, length):
+        # Ordered by y, then z, then x like Sponge schematics
+        self.blocks = np.zeros((height, length, width), np.uint16)
+        self.palette = {"minecraft:air": 0}
+
+    def paletteIndex(self, block):
+        state = blockState(block)
+        if state not in self.palette:
+            self.palette[state] = len(self.palette)
+        return self.palette[state]
+
+    def setBlock(self, block, x, y, z):
+        height, length, width = self.blocks.shape
+        if not (0 <= x < width and 0 <= y < height and 0 <= z < length):
+            return
+        self.blocks[y, z, x] = self.paletteIndex(block)
+
+    def fill(self, block, x1, y1, z1, x2, y2, z2):
+        # Inclusive bounds like fillBlocks, clipped to the recorded area
+        height, length, width = self.blocks.shape
+        x1, y1, z1 = max(x1, 0), max(y1, 0), max(z1, 0)
+        x2, y2, z2 = min(x2, width - 1), min(y2, height - 1), min(z2, length - 1)
+        if x1 > x2 or y1 > y2 or z1 > z2:
+            return
+        self.blocks[y1 : y2 + 1, z1 : z2 + 1, x1 : x2 + 1] = self.paletteIndex(block)
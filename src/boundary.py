@@ -0,0 +1,74 @@
+import json
+import numpy as np
+import matplotlib.path as mplPath
+
+boundaryWayId = -1
+
+
+def loadBoundary(path):
+    with open(path, "r", encoding="utf-8") as f:
+        boundary = json.load(f)
+
+    # A plain list of [lat, lng] vertices
+    if isinstance(boundary, list):
+        return [(vertex[0], vertex[1]) for vertex in boundary]
+
+    if boundary["type"] == "FeatureCollection":
+        boundary = boundary["features"][0]
+    if boundary["type"] == "Feature":
+        boundary = boundary["geometry"]
+
+    ring = boundary["coordinates"][0]
+    if boundary["type"] == "MultiPolygon":
+        ring = boundary["coordinates"][0][0]
+
+    # GeoJSON stores positions as [lng, lat]
+    return [(vertex[1], vertex[0]) for vertex in ring]
+
+
+def getBoundaryBbox(vertices):
+    return (
+        min(vertex[0] for vertex in vertices),
+        min(vertex[1] for vertex in vertices),
+        max(vertex[0] for vertex in vertices),
+        max(vertex[1] for vertex in vertices),
+    )
+
+
+def addBoundary(data, vertices):
+    # The boundary is added as an untagged way, so its vertices go through the
+    # same coordinate normalization as every other node
+    nodeIds = []
+    for i, vertex in enumerate(vertices):
+        nodeId = -(i + 1)
+        data["elements"].append(
+            {
+                "type": "node",
+                "id": nodeId,
+                "lat": round(vertex[0], 7),
+                "lon": round(vertex[1], 7),
+            }
+        )
+        nodeIds.append(nodeId)
+
+    data["elements"].append({"type": "way", "id": boundaryWayId, "nodes": nodeIds})
+
+
+def getBoundaryPolygon(data):
+    for element in data["elements"]:
+        if element["type"] == "way" and element["id"] == boundaryWayId:
+            return [(node[0], node[1]) for node in element["nodes"]]
+    return None
+
+
+def polygonMask(shape, polygon):
+    # Polygon vertices are (x, y) image coordinates, the mask is indexed [y][x]
+    rows, cols = np.mgrid[0 : shape[0], 0 : shape[1]]
+    points = np.column_stack((cols.ravel(), rows.ravel()))
+    inside = mplPath.Path(polygon).contains_points(points)
+    return inside.reshape(shape[0], shape[1])
+
+
+def maskOutsideBoundary(img, polygon):
+    img[~polygonMask(img.shape, polygon)] = 1
+    return img
@@ -0,0 +1,31 @@
+import re
+from datetime import date
+
+# Age classes stored per wall pixel, 0 means the age is unknown
+historic = 1
+old = 2
+modern = 3
+
+
+def getConstructionYear(tags):
+    if "start_date" in tags:
+        # Covers "1890", "1890-05-01", "~1890", "C19" is ignored
+        match = re.search(r"\d{4}", tags["start_date"])
+        if match is not None:
+            return int(match.group(0))
+    if "building:age" in tags and tags["building:age"].isnumeric():
+        return date.today().year - int(tags["building:age"])
+    return None
+
+
+def getAgeClass(tags):
+    year = getConstructionYear(tags)
+    if year is None:
+        return 0
+    if year < 1850:
+        return historic
+    if year < 1950:
+        return old
+    if year >= 1990:
+        return modern
+    return 0
@@ -0,0 +1,164 @@
+import json
+
+from .spatialIndex import GridIndex
+
+# Far away from the ids used by the boundary polygon
+footprintIdOffset = -(10**9)
+# About 100 m, a few buildings per cell in cities
+indexCellSize = 0.001
+
+
+def loadFootprints(path):
+    with open(path, "r", encoding="utf-8") as f:
+        collection = json.load(f)
+
+    footprints = []
+    for feature in collection["features"]:
+        geometry = feature["geometry"]
+        if geometry is None or geometry["type"] not in ("Polygon", "MultiPolygon"):
+            continue
+        ring = geometry["coordinates"][0]
+        if geometry["type"] == "MultiPolygon":
+            ring = geometry["coordinates"][0][0]
+
+        properties = feature.get("properties") or {}
+        height = properties.get("height")
+        # Microsoft footprints use -1 for unknown heights
+        if height is not None and height <= 0:
+            height = None
+        levels = properties.get("num_floors") or properties.get("levels")
+
+        footprints.append(
+            {
+                # GeoJSON stores positions as [lng, lat]
+                "ring": [(vertex[1], vertex[0]) for vertex in ring],
+                "height": height,
+                "levels": levels,
+            }
+        )
+    return footprints
+
+
+def containsPoint(ring, point):
+    inside = False
+    j = len(ring) - 1
+    for i in range(len(ring)):
+        if (ring[i][1] > point[1]) != (ring[j][1] > point[1]):
+            slope = (ring[j][0] - ring[i][0]) / (ring[j][1] - ring[i][1])
+            if point[0] < ring[i][0] + slope * (point[1] - ring[i][1]):
+                inside = not inside
+        j = i
+    return inside
+
+
+def bbox(ring):
+    return (
+        min(vertex[0] for vertex in ring),
+        min(vertex[1] for vertex in ring),
+        max(vertex[0] for vertex in ring),
+        max(vertex[1] for vertex in ring),
+    )
+
+
+def bboxContains(box, point):
+    return box[0] <= point[0] <= box[2] and box[1] <= point[1] <= box[3]
+
+
+def centroid(ring):
+    return (
+        sum(vertex[0] for vertex in ring) / len(ring),
+        sum(vertex[1] for vertex in ring) / len(ring),
+    )
+
+
+def footprintTags(footprint):
+    tags = {"building": "yes"}
+    if footprint["height"] is not None:
+        tags["height"] = str(round(footprint["height"]))
+    if footprint["levels"] is not None:
+        tags["building:levels"] = str(int(footprint["levels"]))
+    return tags
+
+
+def mergeFootprints(data, footprints):
+    nodes = {}
+    for element in data["elements"]:
+        if element["type"] == "node":
+            nodes[element["id"]] = (element["lat"], element["lon"])
+    # Footprints far outside of the OSM data would stretch the generated area
+    dataBbox = bbox(list(nodes.values()))
+
+    buildings = GridIndex(indexCellSize)
+    for element in data["elements"]:
+        if (
+            element["type"] == "way"
+            and "tags" in element
+            and "building" in element["tags"]
+        ):
+            ring = [nodes[node] for node in element["nodes"] if node in nodes]
+            if len(ring) >= 3:
+                buildingBbox = bbox(ring)
+                buildings.insert(
+                    (element, ring, buildingBbox, centroid(ring)), buildingBbox
+                )
+
+    updated = 0
+    added = 0
+    nextId = footprintIdOffset
+    for footprint in footprints:
+        ring = footprint["ring"]
+        if len(ring) < 3:
+            continue
+        footprintBbox = bbox(ring)
+        footprintCentroid = centroid(ring)
+        if not bboxContains(dataBbox, footprintCentroid):
+            continue
+        candidates = buildings.query(footprintBbox)
+        overlapping = [
+            element
+            for element, buildingRing, buildingBbox, buildingCentroid in candidates
+            if (
+                bboxContains(footprintBbox, buildingCentroid)
+                and containsPoint(ring, buildingCentroid)
+            )
+            or (
+                bboxContains(buildingBbox, footprintCentroid)
+                and containsPoint(buildingRing, footprintCentroid)
+            )
+        ]
+
+        if len(overlapping) > 0:
+            for element in overlapping:
+                tags = element["tags"]
+                if "height" in tags or "building:levels" in tags:
+                    continue
+                for key, value in footprintTags(footprint).items():
+                    if key != "building":
+                        tags[key] = value
+                updated += 1
+            continue
+
+        nodeIds = []
+        for vertex in ring:
+            data["elements"].append(
+                {
+                    "type": "node",
+                    "id": nextId,
+                    "lat": round(vertex[0], 7),
+                    "lon": round(vertex[1], 7),
+                }
+            )
+            nodeIds.append(nextId)
+            nextId -= 1
+        data["elements"].append(
+            {
+                "type": "way",
+                "id": nextId,
+                "nodes": nodeIds,
+                "tags": footprintTags(footprint),
+            }
+        )
+        nextId -= 1
+        added += 1
+
+    return updated, added
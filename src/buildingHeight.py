@@ -0,0 +1,100 @@
+import os
+import json
+from math import log
+
+# The last digit of the building IDs encodes the height, so 10 levels is the limit
+maxLevels = 10
+# Roughly the tallest building in the world, used as the top of the rescale curve
+tallestLevels = 163
+rescaleThreshold = 4
+
+
+def parseNumber(value):
+    try:
+        return float(str(value).replace(",", ".").split(" ")[0])
+    except ValueError:
+        return None
+
+
+def rescaleLevels(levels):
+    if levels <= rescaleThreshold:
+        return levels
+    ratio = log(levels / rescaleThreshold) / log(tallestLevels / rescaleThreshold)
+    return rescaleThreshold + (maxLevels - rescaleThreshold) * min(ratio, 1)
+
+
+def loadBuildingStyle(style="default"):
+    # A style is either one of the bundled regional presets or a JSON file
+    if os.path.isfile(style):
+        with open(style, "r", encoding="utf-8") as f:
+            buildingStyle = json.load(f)
+    else:
+        stylesPath = os.path.join(os.path.dirname(__file__), "buildingStyles.json")
+        with open(stylesPath, "r", encoding="utf-8") as f:
+            styles = json.load(f)
+        if style not in styles:
+            print(f"Error! Unknown building style {style}")
+            os._exit(1)
+        buildingStyle = styles[style]
+
+    buildingStyle.setdefault("levels", {})
+    buildingStyle["levels"].setdefault("default", 2)
+    buildingStyle.setdefault("curtainWall", {})
+    buildingStyle["curtainWall"].setdefault("types", [])
+    buildingStyle["curtainWall"].setdefault("minLevels", maxLevels + 1)
+    buildingStyle["curtainWall"].setdefault("mullionSpacing", 4)
+    return buildingStyle
+
+
+def isCurtainWall(element, buildingHeight, buildingStyle):
+    curtainWall = buildingStyle["curtainWall"]
+    return (
+        element["tags"].get("building") in curtainWall["types"]
+        and buildingHeight + 1 >= curtainWall["minLevels"]
+    )
+
+
+def getBuildingHeight(element, rescale=False, buildingStyle=None):
+    tags = element["tags"]
+    buildingHeight = 1
+    if buildingStyle is not None and "height" not in tags:
+        defaultLevels = buildingStyle["levels"]
+        levels = defaultLevels.get(tags.get("building"), defaultLevels["default"])
+        buildingHeight = max(0, min(levels, maxLevels) - 1)
+
+    if "height" in tags:
+        if len(tags["height"]) >= 3:
+            buildingHeight = 9
+        elif len(tags["height"]) == 1:
+            buildingHeight = 2
+        elif tags["height"][:1] == "1":
+            buildingHeight = 3
+        elif tags["height"][:1] == "2":
+            buildingHeight = 6
+        else:
+            buildingHeight = 9
+
+    levels = None
+    if "building:levels" in tags and tags["building:levels"].isnumeric():
+        levels = int(float(tags["building:levels"]))
+    elif "height" in tags and parseNumber(tags["height"]) is not None:
+        levels = parseNumber(tags["height"]) / 3
+
+    if levels is None or levels < 1:
+        return buildingHeight
+
+    if rescale:
+        return max(0, min(round(rescaleLevels(levels)), maxLevels) - 1)
+
+    if levels > maxLevels:
+        name = tags.get("name", "id " + str(element["id"]))
+        print(
+            f"Warning! Building {name} ({round(levels)} levels) exceeds the height "
+            + f"limit of {maxLevels} levels and is truncated"
+        )
+        return maxLevels - 1
+
+    if "building:levels" in tags:
+        return levels - 1
+
+    return buildingHeight
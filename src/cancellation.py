@@ -0,0 +1,31 @@
+import os
+import signal
+
+
+class Cancellation:
+    # The first Ctrl-C stops the generation after the current row, so the
+    # regions can still be saved consistently, the second one quits at once
+    def __init__(self):
+        self.requested = False
+        self.previousHandler = None
+
+    def handle(self, signum, frame):
+        if self.requested:
+            print("Quitting without saving")
+            os._exit(1)
+        self.requested = True
+        print("\nStopping after the current row, press Ctrl-C again to quit")
+
+    def install(self):
+        self.previousHandler = signal.signal(signal.SIGINT, self.handle)
+
+    def restore(self):
+        signal.signal(signal.SIGINT, self.previousHandler)
+
+
+def confirmPartialSave():
+    try:
+        answer = input("Save the partially generated world? [Y/n] ")
+    except EOFError:
+        return True
+    return answer.strip().lower() in ("", "y", "yes")
@@ -0,0 +1,111 @@
+import os
+import json
+import shutil
+
+from .intermediate import loadIntermediate, saveIntermediate
+
+# Options which don't change the downloaded data, the processed data or the
+# generated blocks, so they may differ when resuming
+ignoredOptions = (
+    "debug",
+    "cacheTtl",
+    "noCache",
+    "proxy",
+    "servers",
+    "polite",
+    "contact",
+    "downloadWorkers",
+    "autoTune",
+    "yes",
+    "verifySave",
+    "rconRate",
+    "compressionLevel",
+    "maxMemory",
+    "metricsOut",
+    "checkpoint",
+)
+
+
+class Checkpoint:
+    # Keeps the results of the finished pipeline stages in a directory, so a
+    # crashed or cancelled run continues where it stopped when started again
+    def __init__(self, directory, options):
+        self.directory = directory
+        os.makedirs(directory, exist_ok=True)
+        # Compared after a round trip, e.g. tuples are read back as lists
+        options = json.loads(
+            json.dumps(
+                {
+                    key: value
+                    for key, value in options.items()
+                    if key not in ignoredOptions
+                },
+                default=str,
+            )
+        )
+        self.state = {"options": options, "seed": None, "savedRegions": []}
+        previous = {}
+        if os.path.exists(self.path("checkpoint.json")):
+            with open(self.path("checkpoint.json"), "r", encoding="utf-8") as f:
+                previous = json.load(f)
+        if previous.get("options") == options:
+            self.state = previous
+        elif previous or os.path.exists(self.path("data.json")):
+            print(
+                "Warning! The options changed since the checkpoint was written, "
+                + "starting over"
+            )
+            self.clear()
+        self.saveState()
+
+    def path(self, name):
+        return os.path.join(self.directory, name)
+
+    def saveState(self):
+        with open(self.path("checkpoint.json"), "w", encoding="utf-8") as f:
+            json.dump(self.state, f)
+
+    def clear(self):
+        for name in ("data.json", "processed.npz"):
+            if os.path.exists(self.path(name)):
+                os.remove(self.path(name))
+
+    def loadRawData(self):
+        if not os.path.exists(self.path("data.json")):
+            return None
+        print(f"Resuming with the downloaded data from {self.directory}")
+        with open(self.path("data.json"), "r", encoding="utf-8") as f:
+            return json.load(f)
+
+    def saveRawData(self, rawdata):
+        # Written to a temporary file first, a crash must not leave half a file
+        with open(self.path("data.json.part"), "w", encoding="utf-8") as f:
+            json.dump(rawdata, f)
+        os.replace(self.path("data.json.part"), self.path("data.json"))
+
+    def loadProcessed(self):
+        if not os.path.exists(self.path("processed.npz")):
+            return None
+        return loadIntermediate(self.path("processed.npz"))
+
+    def saveProcessed(self, imgarray, imgAge):
+        # numpy appends .npz to names without it
+        saveIntermediate(self.path("processed.part.npz"), imgarray, imgAge)
+        os.replace(self.path("processed.part.npz"), self.path("processed.npz"))
+
+    def generationSeed(self, seed):
+        # The seed of the interrupted run, the given one when starting
+        if self.state["seed"] is None:
+            self.state["seed"] = seed
+            self.saveState()
+        return self.state["seed"]
+
+    def savedRegions(self):
+        return self.state["savedRegions"]
+
+    def saveRegions(self, keys):
+        self.state["savedRegions"] = list(keys)
+        self.saveState()
+
+    def remove(self):
+        shutil.rmtree(self.directory)
@@ -0,0 +1,137 @@
+# Coastline ways are drawn with the land on the left and the water on the right.
+# The assembly works with (east, north) points, so "clockwise" has its usual
+# meaning, while the processed nodes are stored as (north, east).
+
+
+def joinSegments(ways):
+    chains = [list(way) for way in ways if len(way) >= 2]
+    merged = True
+    while merged:
+        merged = False
+        for i, chain in enumerate(chains):
+            for j, other in enumerate(chains):
+                if i != j and chain[-1] == other[0] and chain[0] != chain[-1]:
+                    chains[i] = chain + other[1:]
+                    del chains[j]
+                    merged = True
+                    break
+            if merged:
+                break
+    return chains
+
+
+def snapToBorder(point, width, height):
+    east, north = point
+    distances = [
+        (north, (east, 0)),
+        (height - north, (east, height)),
+        (east, (0, north)),
+        (width - east, (width, north)),
+    ]
+    return min(distances, key=lambda distance: distance[0])[1]
+
+
+def borderPosition(point, width, height):
+    # Distance along the border, walking clockwise from the north west corner
+    east, north = point
+    if north == height:
+        return east
+    if east == width:
+        return width + (height - north)
+    if north == 0:
+        return width + height + (width - east)
+    return 2 * width + height + north
+
+
+def cornersBetween(start, distance, width, height):
+    perimeter = 2 * (width + height)
+    corners = [
+        (0, (0, height)),
+        (width, (width, height)),
+        (width + height, (width, 0)),
+        (2 * width + height, (0, 0)),
+    ]
+    passed = [
+        ((position - start) % perimeter, corner)
+        for position, corner in corners
+        if 0 < (position - start) % perimeter < distance
+    ]
+    return [corner for _, corner in sorted(passed)]
+
+
+def closeAlongBorder(chains, width, height):
+    perimeter = 2 * (width + height)
+    remaining = list(chains)
+    polygons = []
+    while remaining:
+        first = remaining.pop(0)
+        polygon = list(first)
+        current = first
+        while True:
+            end = borderPosition(current[-1], width, height)
+            candidates = [
+                ((borderPosition(chain[0], width, height) - end) % perimeter, i)
+                for i, chain in enumerate(remaining)
+            ]
+            candidates.append(
+                ((borderPosition(first[0], width, height) - end) % perimeter, None)
+            )
+            distance, nextChain = min(candidates, key=lambda candidate: candidate[0])
+            polygon += cornersBetween(end, distance, width, height)
+            if nextChain is None:
+                break
+            current = remaining.pop(nextChain)
+            polygon += current
+        polygons.append(polygon)
+    return polygons
+
+
+def signedArea(ring):
+    area = 0
+    for a, b in zip(ring, ring[1:] + ring[:1]):
+        area += a[0] * b[1] - b[0] * a[1]
+    return area / 2
+
+
+def splitRings(coastlines):
+    ways = [[(node[1], node[0]) for node in coastline] for coastline in coastlines]
+    openChains = []
+    islands = []
+    seas = []
+    for chain in joinSegments(ways):
+        if chain[0] != chain[-1]:
+            openChains.append(chain)
+        # Land is on the left, so rings around an island run counterclockwise
+        elif signedArea(chain) > 0:
+            islands.append(chain)
+        else:
+            seas.append(chain)
+    return openChains, islands, seas
+
+
+def getIslandPolygons(coastlines):
+    _, islands, _ = splitRings(coastlines)
+    return [[(point[1], point[0]) for point in island] for island in islands]
+
+
+def getOceanPolygons(coastlines, sizeX, sizeY):
+    width = sizeY - 1
+    height = sizeX - 1
+    chains, islands, seas = splitRings(coastlines)
+
+    openChains = []
+    for chain in chains:
+        start = snapToBorder(chain[0], width, height)
+        end = snapToBorder(chain[-1], width, height)
+        openChains.append(
+            ([start] if start != chain[0] else [])
+            + chain
+            + ([end] if end != chain[-1] else [])
+        )
+
+    polygons = closeAlongBorder(openChains, width, height)
+    if len(openChains) == 0 and len(islands) > 0:
+        # No coastline crosses the area, so everything around the islands is sea
+        polygons.append([(0, 0), (width, 0), (width, height), (0, height)])
+    polygons += seas
+    return [[(point[1], point[0]) for point in polygon] for polygon in polygons]
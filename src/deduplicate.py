@@ -0,0 +1,29 @@
+def geometryKey(element):
+    nodes = tuple(element["nodes"])
+    # A way and its reversed copy describe the same geometry
+    nodes = min(nodes, tuple(reversed(nodes)))
+    tags = tuple(sorted(element.get("tags", {}).items()))
+    return (nodes, tags)
+
+
+def deduplicate(data):
+    seenIds = set()
+    seenGeometries = set()
+    elements = []
+    for element in data["elements"]:
+        identifier = (element["type"], element["id"])
+        if identifier in seenIds:
+            continue
+        seenIds.add(identifier)
+
+        if element["type"] == "way":
+            key = geometryKey(element)
+            if key in seenGeometries:
+                continue
+            seenGeometries.add(key)
+
+        elements.append(element)
+
+    removed = len(data["elements"]) - len(elements)
+    data["elements"] = elements
+    return removed
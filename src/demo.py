@@ -0,0 +1,10 @@
+import os
+import json
+
+demoPath = os.path.join(os.path.dirname(__file__), "demo.json")
+
+
+def getDemoData(path=demoPath):
+    print("Loading offline demo data...")
+    with open(path, "r", encoding="utf-8") as f:
+        return json.load(f)
@@ -0,0 +1,55 @@
+import threading
+import time
+from concurrent.futures import ThreadPoolExecutor
+from urllib.parse import urlparse
+import requests
+
+from . import politeness
+from .progress import Progress
+
+# Be polite to tile servers, regardless of the number of workers
+requestsPerSecond = 8
+
+
+class DownloadManager:
+    def __init__(self, workers=4):
+        self.workers = workers
+        self.lock = threading.Lock()
+        self.nextRequest = {}
+        self.done = 0
+        self.progress = None
+
+    def waitForHost(self, host):
+        rate = requestsPerSecond
+        if politeness.polite:
+            rate = politeness.politeRequestsPerSecond
+        with self.lock:
+            now = time.time()
+            start = max(now, self.nextRequest.get(host, now))
+            self.nextRequest[host] = start + 1 / rate
+        time.sleep(start - now)
+
+    def reportProgress(self):
+        with self.lock:
+            self.done += 1
+            self.progress.update(self.done)
+
+    def get(self, url):
+        self.waitForHost(urlparse(url).netloc)
+        try:
+            response = requests.get(
+                url, headers=politeness.requestHeaders(), timeout=30
+            )
+            response.raise_for_status()
+            return response.content
+        except requests.exceptions.RequestException as e:
+            print(f"Warning! Could not download {url}: {e}")
+            return None
+        finally:
+            self.reportProgress()
+
+    def download(self, urls):
+        self.done = 0
+        self.progress = Progress("Downloaded", len(urls))
+        with ThreadPoolExecutor(max_workers=self.workers) as executor:
+            return list(executor.map(self.get, urls))
@@ -0,0 +1,19 @@
+from .bresenham import bresenham
+
+ferryCode = 28
+waterCodes = (38, 40)  # Water, ocean
+
+
+def drawFerries(img, data):
+    for element in data["elements"]:
+        if element["type"] != "way" or element.get("tags", {}).get("route") != "ferry":
+            continue
+        previous = None
+        for node in element["nodes"]:
+            if previous is not None:
+                # Only the crossing itself, the docks are already mapped as roads
+                for x, y in bresenham(node[0], node[1], previous[0], previous[1]):
+                    if img[y][x] in waterCodes:
+                        img[y][x] = ferryCode
+            previous = node
+    return img
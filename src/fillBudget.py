@@ -0,0 +1,49 @@
+from time import time
+
+from .floodFill import floodFill
+from .scanlineFill import scanlineFill
+
+defaultTimeout = 7
+degradedTimeout = 0.5
+
+
+class FillBudget:
+    def __init__(self, fills, totalSeconds=None, algorithm="scanline"):
+        if totalSeconds is None:
+            totalSeconds = fills * defaultTimeout
+        self.remaining = totalSeconds
+        self.fillsLeft = fills
+        self.truncated = 0
+        self.exhausted = False
+        self.algorithm = algorithm
+
+    def allowance(self):
+        if self.remaining <= 0:
+            if not self.exhausted:
+                print("Warning! Floodfill budget exhausted, areas may be incomplete")
+                self.exhausted = True
+            return degradedTimeout
+
+        # Time saved by small areas is handed on to the large ones
+        fairShare = self.remaining / max(self.fillsLeft, 1)
+        return min(self.remaining, max(defaultTimeout, fairShare))
+
+    def fill(self, *args, **kwargs):
+        if self.algorithm == "scanline":
+            # The scanline fill can't leak out of an area, so it needs no
+            # timeout. Areas its rows miss, e.g. thinner than a block, fall
+            # back to the floodfill, which uses the budget.
+            filled = scanlineFill(*args, **kwargs)
+            if filled != 0:
+                self.fillsLeft = max(self.fillsLeft - 1, 0)
+                return args[0]
+        timeout = self.allowance()
+        startTime = time()
+        img = floodFill(*args, timeout=timeout, **kwargs)
+        elapsed = time() - startTime
+
+        self.remaining -= elapsed
+        self.fillsLeft = max(self.fillsLeft - 1, 0)
+        if elapsed >= timeout:
+            self.truncated += 1
+        return img
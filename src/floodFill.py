@@ -4,13 +4,11 @@ import matplotlib.path as mplPath
 from polylabel import polylabel
 
 
-def floodFill(
-    img, px, py, newColor, currentBuilding, minMaxDistX, minMaxDistY, elementType="None"
-):
-    startTimeFloodfill = time()
-    currentBuilding = np.delete(currentBuilding, 0, axis=0)
+def findSeed(img, px, py, currentBuilding, minMaxDistX, minMaxDistY):
+    # Moves the start point into the area and off its outline, None if the
+    # area is degenerate or no start point is found
     if len(currentBuilding) <= 2 or not (px < minMaxDistY and py < minMaxDistX):
-        return img
+        return None
     if not (mplPath.Path(currentBuilding).contains_point((py, px))):
         centroid = polylabel([currentBuilding.tolist()], with_distance=True)
         px = round(centroid[0][1])
@@ -25,9 +23,9 @@ def floodFill(
             elif mplPath.Path(currentBuilding).contains_point((py, px + 5)):
                 px += 5
             else:
-                return img
+                return None
 
-    if str(img[px][py][0])[:1] == "5" or str(img[px][py][0])[:1] == "6":
+    if str(img[px][py][0])[:1] in ("5", "6", "8"):
         if mplPath.Path(currentBuilding).contains_point((py - 1, px)):
             py -= 1
         elif mplPath.Path(currentBuilding).contains_point((py + 1, px)):
@@ -37,7 +35,27 @@ def floodFill(
         elif mplPath.Path(currentBuilding).contains_point((py, px + 1)):
             px += 1
         else:
-            return img
+            return None
+    return px, py
+
+
+def floodFill(
+    img,
+    px,
+    py,
+    newColor,
+    currentBuilding,
+    minMaxDistX,
+    minMaxDistY,
+    elementType="None",
+    timeout=7,
+):
+    startTimeFloodfill = time()
+    currentBuilding = np.delete(currentBuilding, 0, axis=0)
+    seed = findSeed(img, px, py, currentBuilding, minMaxDistX, minMaxDistY)
+    if seed is None:
+        return img
+    px, py = seed
 
     try:
         oldColor = img[px][py][0]
@@ -96,7 +114,7 @@ def floodFill(
                 nxt.append((x, y + 1))
 
             # Timeout (known issue, see Github readme)
-            if time() - startTimeFloodfill > 7 or (
+            if time() - startTimeFloodfill > timeout or (
                 elementType == "tree_row" and time() - startTimeFloodfill > 0.2
             ):
                 return img
@@ -0,0 +1,60 @@
+import os
+from math import cos, radians
+import requests
+
+from . import politeness
+
+nominatimUrl = "https://nominatim.openstreetmap.org/search"
+
+
+def geocode(city, state, country, proxy=None):
+    print("Looking up area...")
+    proxies = None
+    if proxy is not None:
+        proxies = {"http": proxy, "https": proxy}
+    try:
+        response = requests.get(
+            nominatimUrl,
+            params={
+                "city": city,
+                "state": state,
+                "country": country,
+                "format": "json",
+                "limit": 1,
+            },
+            # Required by the Nominatim usage policy
+            headers=politeness.requestHeaders(),
+            timeout=60,
+            proxies=proxies,
+        )
+        response.raise_for_status()
+        results = response.json()
+    except requests.exceptions.RequestException as e:
+        print(f"Error! Could not look up the area: {e}")
+        os._exit(1)
+
+    if len(results) == 0:
+        print(f"Error! {city}, {state}, {country} could not be found")
+        os._exit(1)
+
+    south, north, west, east = (float(value) for value in results[0]["boundingbox"])
+    return results[0]["display_name"], (south, west, north, east)
+
+
+def describeBbox(bbox):
+    south, west, north, east = bbox
+    height = (north - south) * 111.32
+    width = (east - west) * 111.32 * cos(radians((north + south) / 2))
+    return f"{south}, {west}, {north}, {east} ({width:.1f} x {height:.1f} km)"
+
+
+def confirmArea(name, bbox):
+    print(f"Found {name}")
+    print(f"Bounding box: {describeBbox(bbox)}")
+    try:
+        answer = input("Generate this area? [Y/n] ")
+    except EOFError:
+        # No terminal to ask, e.g. in a container started without -it
+        print("\nError! Can't ask for confirmation, pass --yes to skip it")
+        os._exit(1)
+    return answer.strip().lower() in ("", "y", "yes")
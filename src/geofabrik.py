@@ -0,0 +1,113 @@
+import os
+import time
+import requests
+import osmium
+
+from . import politeness
+
+indexUrl = "https://download.geofabrik.de/index-v1-nogeom.json"
+downloadDirectory = "arnis-cache"
+
+
+def findExtractUrl(region, proxies=None):
+    index = requests.get(
+        indexUrl, headers=politeness.requestHeaders(), timeout=60, proxies=proxies
+    ).json()
+    for feature in index["features"]:
+        if feature["properties"]["id"] == region:
+            return feature["properties"]["urls"]["pbf"]
+
+    print(f"Error! No Geofabrik extract named {region}")
+    os._exit(1)
+
+
+def isFresh(path, cacheTtl):
+    # Geofabrik updates its extracts daily, like the Overpass cache expires
+    if cacheTtl <= 0 or not os.path.exists(path):
+        return False
+    return time.time() - os.path.getmtime(path) <= cacheTtl * 3600
+
+
+def downloadExtract(region, cacheTtl=24, proxy=None):
+    os.makedirs(downloadDirectory, exist_ok=True)
+    extractPath = os.path.join(downloadDirectory, region + "-latest.osm.pbf")
+    if isFresh(extractPath, cacheTtl):
+        print(f"Using downloaded extract {extractPath}")
+        return extractPath
+
+    proxies = None
+    if proxy is not None:
+        proxies = {"http": proxy, "https": proxy}
+    url = findExtractUrl(region, proxies)
+    print(f"Downloading {url}...")
+    with requests.get(
+        url,
+        stream=True,
+        headers=politeness.requestHeaders(),
+        timeout=60,
+        proxies=proxies,
+    ) as response:
+        response.raise_for_status()
+        with open(extractPath + ".part", "wb") as f:
+            for block in response.iter_content(chunk_size=1024 * 1024):
+                f.write(block)
+    os.replace(extractPath + ".part", extractPath)
+    return extractPath
+
+
+class BboxHandler(osmium.SimpleHandler):
+    def __init__(self, bbox):
+        super().__init__()
+        self.bbox = bbox
+        self.nodes = {}
+        self.ways = []
+
+    def inBbox(self, location):
+        return (
+            self.bbox[0] <= location.lat <= self.bbox[2]
+            and self.bbox[1] <= location.lon <= self.bbox[3]
+        )
+
+    def way(self, w):
+        tags = {tag.k: tag.v for tag in w.tags}
+        # Same filter as the Overpass query
+        if "power" in tags or "place" in tags:
+            return
+        if not any(
+            node.location.valid() and self.inBbox(node.location) for node in w.nodes
+        ):
+            return
+
+        for node in w.nodes:
+            # Round to the OSM precision, coordinates are later parsed as digits
+            self.nodes[node.ref] = (
+                round(node.location.lat, 7),
+                round(node.location.lon, 7),
+            )
+        self.ways.append(
+            {
+                "type": "way",
+                "id": w.id,
+                "nodes": [node.ref for node in w.nodes],
+                "tags": tags,
+            }
+        )
+
+
+def getGeofabrikData(region, bbox, cacheTtl=24, proxy=None):
+    print("Fetching data...")
+    extractPath = downloadExtract(region, cacheTtl, proxy)
+
+    print("Extracting area...")
+    handler = BboxHandler(bbox)
+    handler.apply_file(extractPath, locations=True)
+    if len(handler.ways) == 0:
+        print("Error! No data available")
+        os._exit(1)
+
+    # Mirror the structure of an Overpass JSON response
+    elements = [
+        {"type": "node", "id": nodeId, "lat": location[0], "lon": location[1]}
+        for nodeId, location in handler.nodes.items()
+    ]
+    return {"elements": elements + handler.ways}
@@ -0,0 +1,26 @@
+from math import log10
+
+
+class Georeference:
+    # Inverts the coordinate normalization of processData, which strips the
+    # decimal point of every coordinate, pads them to the same number of digits
+    # and shifts them to the origin before scaling them down to pixels
+    def __init__(self, scale, lowest, minBuilding, resDownScaler):
+        self.scale = tuple(10 ** round(log10(factor)) for factor in scale)
+        self.offset = (
+            lowest[0] + minBuilding[0] * resDownScaler,
+            lowest[1] + minBuilding[1] * resDownScaler,
+        )
+        self.resDownScaler = resDownScaler
+
+    def latitude(self, x):
+        return (x * self.resDownScaler + self.offset[0]) / self.scale[0]
+
+    def longitude(self, y):
+        return (y * self.resDownScaler + self.offset[1]) / self.scale[1]
+
+    def toPixel(self, lat, lon):
+        return (
+            round((lat * self.scale[0] - self.offset[0]) / self.resDownScaler),
+            round((lon * self.scale[1] - self.offset[1]) / self.resDownScaler),
+        )
@@ -1,58 +1,158 @@
 import os
+import json
+import time
+import hashlib
 import requests
-from random import choice
+from random import shuffle
 
+from . import politeness
 
-def getData(city, state, country, debug):
+try:
+    import orjson
+except ImportError:  # Optional, the standard library parser is used instead
+    orjson = None
+
+cacheDirectory = "arnis-cache"
+defaultServers = [
+    "https://overpass-api.de/api/interpreter",
+    "https://lz4.overpass-api.de/api/interpreter",
+    "https://z.overpass-api.de/api/interpreter",
+    "https://maps.mail.ru/osm/tools/overpass/api/interpreter",
+    "https://overpass.openstreetmap.ru/api/interpreter",
+    "https://overpass.kumi.systems/api/interpreter",
+]
+
+
+def loadServerConfig(path):
+    # {"overpass": [...], "proxy": "http://..."}, missing keys keep the defaults
+    with open(path, "r", encoding="utf-8") as f:
+        config = json.load(f)
+    return config.get("overpass", defaultServers), config.get("proxy")
+
+
+def getCachePath(query):
+    queryHash = hashlib.sha256(query.encode("utf-8")).hexdigest()
+    return os.path.join(cacheDirectory, queryHash + ".json")
+
+
+def parseJson(content):
+    # orjson parses the response bytes directly, several times faster and
+    # without decoding them into an intermediate str first
+    if orjson is not None:
+        return orjson.loads(content)
+    return json.loads(content)
+
+
+def loadCache(query, cacheTtl):
+    cachePath = getCachePath(query)
+    if not os.path.exists(cachePath):
+        return None
+    if time.time() - os.path.getmtime(cachePath) > cacheTtl * 3600:
+        os.remove(cachePath)
+        return None
+    with open(cachePath, "rb") as f:
+        return parseJson(f.read())
+
+
+def saveCache(query, data):
+    os.makedirs(cacheDirectory, exist_ok=True)
+    with open(getCachePath(query), "w", encoding="utf-8") as f:
+        json.dump(data, f)
+
+
+def describeError(e):
+    if "The server is probably too busy to handle your request." in str(e):
+        return "OSM server overloaded"
+    elif "Dispatcher_Client::request_read_and_idx::rate_limited" in str(e):
+        return "IP rate limited"
+    return str(e)[:200]
+
+
+def requestData(url, query, proxies):
+    response = requests.get(
+        url,
+        params={"data": query},
+        headers=politeness.requestHeaders(),
+        timeout=300,
+        proxies=proxies,
+    )
+    if response.status_code != 200:
+        raise Exception(f"HTTP {response.status_code} {response.text}")
+    data = parseJson(response.content)
+
+    # Overpass answers timeouts and memory exhaustion with a partial result
+    if "remark" in data and "error" in data["remark"]:
+        raise Exception(f"Partial result ({data['remark']})")
+    if len(data["elements"]) == 0:
+        raise LookupError("No data available")
+    return data
+
+
+def fetchData(query, servers=defaultServers, proxy=None, retries=2):
+    api_servers = list(servers)
+    shuffle(api_servers)
+    # Without an explicit proxy, requests picks up HTTP(S)_PROXY from the environment
+    proxies = None
+    if proxy is not None:
+        proxies = {"http": proxy, "https": proxy}
+
+    for attempt in range(retries + 1):
+        for i, url in enumerate(api_servers):
+            if attempt == 0 and i == 0:
+                print(f"Chosen server: {url}")
+            else:
+                print(f"Retrying on mirror {i + 1}/{len(api_servers)}: {url}")
+
+            try:
+                return requestData(url, query, proxies)
+            except LookupError as e:
+                print(f"Error! {e}")
+                os._exit(1)
+            except Exception as e:
+                print(f"Error! {describeError(e)}")
+
+            # No need to wait after the last mirror of the last attempt
+            if attempt < retries or i < len(api_servers) - 1:
+                time.sleep(
+                    max(min(2 ** (attempt + i), 60), politeness.minimumDelay())
+                )
+
+    print("Error! All servers failed, please try again later")
+    os._exit(1)
+
+
+def getData(bbox, debug, cacheTtl=24, servers=defaultServers, proxy=None):
     print("Fetching data...")
-    api_servers = [
-        "https://overpass-api.de/api/interpreter",
-        "https://lz4.overpass-api.de/api/interpreter",
-        "https://z.overpass-api.de/api/interpreter",
-        "https://maps.mail.ru/osm/tools/overpass/api/interpreter",
-        "https://overpass.openstreetmap.ru/api/interpreter",
-        "https://overpass.kumi.systems/api/interpreter",
-    ]
-    url = choice(api_servers)
-    query1 = (
-        """
+    south, west, north, east = bbox
+    area = f"({south},{west},{north},{east})"
+    query1 = f"""
         [out:json];
-        area[name="""
-        + '"'
-        + city
-        + '"'
-        + """]->.city;
-        area[name="""
-        + '"'
-        + state
-        + '"'
-        + """]->.state;
-        area[name="""
-        + '"'
-        + country
-        + '"'
-        + """]->.country;
-        way(area.country)(area.state)(area.city)[!power][!place][!ferry];
+        (
+            way{area}[!power][!place];
+            node{area}[natural~"^(rock|stone)$"];
+            node{area}[tourism=viewpoint];
+            node{area}[name];
+            way{area}[power~"^(line|minor_line)$"];
+            node{area}[power~"^(tower|pole)$"];
+            node{area}[highway~"^(street_lamp|crossing)$"];
+            relation{area}[type=multipolygon];
+        );
         (._;>;);
         out;
+        relation{area}[type!=multipolygon];
+        out tags;
     """
-    )
 
-    print(f"Chosen server: {url}")
-    try:
-        data = requests.get(url, params={"data": query1}).json()
-
-        if len(data["elements"]) == 0:
-            print("Error! No data available")
-            os._exit(1)
-    except Exception as e:
-        if "The server is probably too busy to handle your request." in str(e):
-            print("Error! OSM server overloaded")
-        elif "Dispatcher_Client::request_read_and_idx::rate_limited" in str(e):
-            print("Error! IP rate limited")
-        else:
-            print(f"Error! {e}")
-        os._exit(1)
+    data = None
+    if cacheTtl > 0:
+        data = loadCache(query1, cacheTtl)
+        if data is not None:
+            print("Using cached data")
+
+    if data is None:
+        data = fetchData(query1, servers, proxy)
+        if cacheTtl > 0:
+            saveCache(query1, data)
 
     if debug:
         with open("arnis-debug-raw_data.json", "w", encoding="utf-8") as f:
@@ -0,0 +1,99 @@
+import json
+import base64
+import numpy as np
+
+from .meshing import materialColor, meshQuads
+
+floatType = 5126
+indexType = 5125  # Unsigned int
+
+
+class BufferBuilder:
+    def __init__(self):
+        self.data = bytearray()
+        self.bufferViews = []
+        self.accessors = []
+
+    def add(self, array, componentType, accessorType, target):
+        # All components are 4 bytes, so every view stays aligned
+        self.bufferViews.append(
+            {
+                "buffer": 0,
+                "byteOffset": len(self.data),
+                "byteLength": array.nbytes,
+                "target": target,
+            }
+        )
+        self.data += array.tobytes()
+        accessor = {
+            "bufferView": len(self.bufferViews) - 1,
+            "componentType": componentType,
+            "count": len(array),
+            "type": accessorType,
+        }
+        if accessorType == "VEC3":
+            accessor["min"] = array.min(axis=0).tolist()
+            accessor["max"] = array.max(axis=0).tolist()
+        self.accessors.append(accessor)
+        return len(self.accessors) - 1
+
+
+def saveGltf(recorder, path):
+    quads = meshQuads(recorder.blocks)
+    states = {index: state for state, index in recorder.palette.items()}
+    builder = BufferBuilder()
+    materials = []
+    primitives = []
+    for material in sorted(quads):
+        faces = quads[material]
+        positions = np.array(
+            [corner for corners, _ in faces for corner in corners], np.float32
+        )
+        normals = np.repeat(np.array([normal for _, normal in faces], np.float32), 4, 0)
+        indices = (
+            np.arange(len(faces), dtype=np.uint32)[:, np.newaxis] * 4
+            + np.array([0, 1, 2, 0, 2, 3], np.uint32)
+        ).ravel()
+
+        color = materialColor(states[material])
+        materials.append(
+            {
+                "name": states[material],
+                "pbrMetallicRoughness": {
+                    "baseColorFactor": [value / 255 for value in color] + [1],
+                    "metallicFactor": 0,
+                },
+            }
+        )
+        primitives.append(
+            {
+                "attributes": {
+                    "POSITION": builder.add(positions, floatType, "VEC3", 34962),
+                    "NORMAL": builder.add(normals, floatType, "VEC3", 34962),
+                },
+                "indices": builder.add(indices, indexType, "SCALAR", 34963),
+                "material": len(materials) - 1,
+            }
+        )
+
+    gltf = {
+        "asset": {"version": "2.0", "generator": "Arnis"},
+        "scene": 0,
+        "scenes": [{"nodes": [0]}],
+        "nodes": [{"mesh": 0}],
+        "meshes": [{"primitives": primitives}],
+        "materials": materials,
+        "buffers": [
+            {
+                "byteLength": len(builder.data),
+                "uri": "data:application/octet-stream;base64,"
+                + base64.b64encode(builder.data).decode("ascii"),
+            }
+        ],
+        "bufferViews": builder.bufferViews,
+        "accessors": builder.accessors,
+    }
+    with open(path, "w", encoding="utf-8") as f:
+        json.dump(gltf, f)
+    faceCount = sum(len(faces) for faces in quads.values())
+    print(f"Saved 3D model {path} ({faceCount} faces)")
@@ -0,0 +1,59 @@
+import xml.etree.ElementTree as ET
+
+from .bresenham import bresenham
+
+trackCode = 15
+waypointCode = 16
+
+
+def localName(tag):
+    return tag.split("}")[-1]
+
+
+def loadGpx(path):
+    root = ET.parse(path).getroot()
+    tracks = []
+    waypoints = []
+    for element in root.iter():
+        name = localName(element.tag)
+        if name in ("trkseg", "rte"):
+            tracks.append(
+                [
+                    (float(point.get("lat")), float(point.get("lon")))
+                    for point in element
+                    if localName(point.tag) in ("trkpt", "rtept")
+                ]
+            )
+        elif name == "wpt":
+            waypoints.append((float(element.get("lat")), float(element.get("lon"))))
+    return tracks, waypoints
+
+
+def isTrackable(img, x, y):
+    # Tracks are laid on the ground, but never through buildings
+    return (
+        0 <= y < img.shape[0]
+        and 0 <= x < img.shape[1]
+        and img[y][x] != 1
+        and not 50 <= img[y][x] <= 89
+    )
+
+
+def drawGpx(img, paths, georeference):
+    for path in paths:
+        tracks, waypoints = loadGpx(path)
+        for track in tracks:
+            previous = None
+            for lat, lon in track:
+                point = georeference.toPixel(lat, lon)
+                if previous is not None:
+                    for x, y in bresenham(point[0], point[1], previous[0], previous[1]):
+                        if isTrackable(img, x, y):
+                            img[y][x] = trackCode
+                previous = point
+
+        for lat, lon in waypoints:
+            x, y = georeference.toPixel(lat, lon)
+            if isTrackable(img, x, y):
+                img[y][x] = waypointCode
+    return img
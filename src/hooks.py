@@ -0,0 +1,59 @@
+import os
+import importlib
+
+postProcessingPasses = []
+
+
+def registerPostProcessingPass(postProcessingPass):
+    # Can be used as a decorator, passes run in the order they were registered
+    postProcessingPasses.append(postProcessingPass)
+    return postProcessingPass
+
+
+def loadPlugins(modules):
+    # Loaded before generating, so a broken plugin doesn't waste a whole run
+    for module in modules:
+        try:
+            importlib.import_module(module)
+        except Exception as e:
+            print(f"Error! Could not load plugin {module}: {e}")
+            os._exit(1)
+
+
+class WorldEditor:
+    """Block access for post-processing passes, limited to the generated area"""
+
+    def __init__(self, setBlock, fillBlocks, imgarray):
+        self._setBlock = setBlock
+        self._fillBlocks = fillBlocks
+        self.imgarray = imgarray
+        self.sizeX = len(imgarray)
+        self.sizeZ = len(imgarray[0])
+
+    def contains(self, x, z):
+        return 0 <= x < self.sizeX and 0 <= z < self.sizeZ
+
+    def setBlock(self, block, x, y, z):
+        if self.contains(x, z):
+            self._setBlock(block, x, y, z)
+
+    def fillBlocks(self, block, x1, y1, z1, x2, y2, z2):
+        x1, x2 = max(x1, 0), min(x2, self.sizeX - 1)
+        z1, z2 = max(z1, 0), min(z2, self.sizeZ - 1)
+        self._fillBlocks(block, x1, y1, z1, x2, y2, z2)
+
+
+def runPostProcessingPasses(setBlock, fillBlocks, imgarray):
+    if len(postProcessingPasses) == 0:
+        return
+    print("Running post-processing passes...")
+    editor = WorldEditor(setBlock, fillBlocks, imgarray)
+    for postProcessingPass in postProcessingPasses:
+        # The generated world is still saved without the failed pass
+        try:
+            postProcessingPass(editor)
+        except Exception as e:
+            print(
+                f"Warning! Post-processing pass {postProcessingPass.__name__} "
+                + f"failed: {e}"
+            )
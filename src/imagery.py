@@ -0,0 +1,72 @@
+from math import asinh, pi, radians, tan
+import cv2
+import numpy as np
+
+from .downloads import DownloadManager
+
+tileSize = 256
+
+# Ground codes 41-49 with the average color of their block (RGB)
+groundPalette = [
+    (41, (210, 178, 161)),  # White terracotta
+    (42, (135, 107, 98)),  # Light gray terracotta
+    (43, (77, 51, 36)),  # Brown terracotta
+    (44, (76, 83, 42)),  # Green terracotta
+    (45, (152, 94, 68)),  # Terracotta
+    (46, (55, 58, 62)),  # Gray concrete
+    (47, (125, 125, 115)),  # Light gray concrete
+    (48, (207, 213, 214)),  # White concrete
+    (49, (103, 117, 53)),  # Lime terracotta
+]
+
+
+def tileX(lon, zoom):
+    return (lon + 180) / 360 * 2**zoom * tileSize
+
+
+def tileY(lat, zoom):
+    return (1 - asinh(tan(radians(lat))) / pi) / 2 * 2**zoom * tileSize
+
+
+def decodeTile(content):
+    if content is None:
+        return None
+    return cv2.imdecode(np.frombuffer(content, np.uint8), cv2.IMREAD_COLOR)
+
+
+def nearestGround(colors):
+    palette = np.array([color for _, color in groundPalette], np.int32)
+    # OpenCV decodes images as BGR
+    rgb = colors[..., ::-1].astype(np.int32)
+    distances = ((rgb[..., np.newaxis, :] - palette) ** 2).sum(axis=-1)
+    codes = np.array([code for code, _ in groundPalette], np.uint8)
+    return codes[distances.argmin(axis=-1)]
+
+
+def sampleImagery(urlTemplate, zoom, georeference, shape, workers=4):
+    # Image rows follow the longitude and columns the latitude
+    pixelX = np.array(
+        [int(tileX(georeference.longitude(y), zoom)) for y in range(shape[0])]
+    )
+    pixelY = np.array(
+        [int(tileY(georeference.latitude(x), zoom)) for x in range(shape[1])]
+    )
+    tiles = [
+        (tx, ty)
+        for tx in np.unique(pixelX // tileSize)
+        for ty in np.unique(pixelY // tileSize)
+    ]
+    contents = DownloadManager(workers).download(
+        [urlTemplate.format(z=zoom, x=tx, y=ty) for tx, ty in tiles]
+    )
+
+    ground = np.zeros(shape[:2], np.uint8)
+    for (tx, ty), content in zip(tiles, contents):
+        tile = decodeTile(content)
+        if tile is None:
+            continue
+        rows = np.nonzero(pixelX // tileSize == tx)[0]
+        columns = np.nonzero(pixelY // tileSize == ty)[0]
+        colors = tile[np.ix_(pixelY[columns] % tileSize, pixelX[rows] % tileSize)]
+        ground[np.ix_(rows, columns)] = nearestGround(colors.transpose(1, 0, 2))
+    return ground
@@ -0,0 +1,21 @@
+import os
+import numpy as np
+
+# Increase whenever the meaning of the processed image codes changes
+intermediateVersion = 1
+
+
+def saveIntermediate(path, imgarray, imgAge):
+    np.savez_compressed(
+        path, version=intermediateVersion, imgarray=imgarray, imgAge=imgAge
+    )
+    print(f"Saved processed data to {path}")
+
+
+def loadIntermediate(path):
+    with np.load(path) as intermediate:
+        if int(intermediate["version"]) != intermediateVersion:
+            print(f"Error! {path} was saved by an incompatible version of Arnis")
+            os._exit(1)
+        print(f"Using processed data from {path}")
+        return intermediate["imgarray"], intermediate["imgAge"]
@@ -0,0 +1,58 @@
+import os
+import re
+import cv2
+import numpy as np
+
+# ESA WorldCover classes mapped to the landuse codes of processData. Built-up,
+# bare and snow covered areas are left to the default ground.
+worldCoverClasses = {
+    10: 32,  # Tree cover -> Forest
+    20: 30,  # Shrubland -> Meadow
+    30: 30,  # Grassland -> Meadow
+    40: 31,  # Cropland -> Farmland
+    80: 38,  # Permanent water bodies -> Water
+    90: 35,  # Herbaceous wetland -> Wetland
+    95: 35,  # Mangroves -> Wetland
+    100: 39,  # Moss and lichen -> Raw grass
+}
+tileDegrees = 3
+
+
+def tileOrigin(path):
+    # Tiles are named after their south west corner, e.g. ..._N51E003_Map.tif
+    match = re.search(r"([NS])(\d{2})([EW])(\d{3})", os.path.basename(path))
+    if match is None:
+        print(f"Error! Cannot read the tile position from {path}")
+        os._exit(1)
+    south = int(match.group(2)) * (1 if match.group(1) == "N" else -1)
+    west = int(match.group(4)) * (1 if match.group(3) == "E" else -1)
+    return south + tileDegrees, west
+
+
+def sampleLandcover(paths, georeference, shape):
+    lookup = np.zeros(256, np.uint8)
+    for worldCoverClass, landuse in worldCoverClasses.items():
+        lookup[worldCoverClass] = landuse
+
+    # Image rows follow the longitude and columns the latitude
+    longitudes = georeference.longitude(np.arange(shape[0]))
+    latitudes = georeference.latitude(np.arange(shape[1]))
+    landcover = np.zeros(shape[:2], np.uint8)
+    for path in paths:
+        north, west = tileOrigin(path)
+        tile = cv2.imread(path, cv2.IMREAD_UNCHANGED)
+        if tile is None:
+            print(f"Warning! Could not read landcover tile {path}")
+            continue
+        pixelSize = tileDegrees / tile.shape[0]
+
+        tileRows = np.floor((north - latitudes) / pixelSize).astype(int)
+        tileColumns = np.floor((longitudes - west) / pixelSize).astype(int)
+        columns = np.nonzero((tileRows >= 0) & (tileRows < tile.shape[0]))[0]
+        rows = np.nonzero((tileColumns >= 0) & (tileColumns < tile.shape[1]))[0]
+        if len(rows) == 0 or len(columns) == 0:
+            continue
+
+        samples = tile[np.ix_(tileRows[columns], tileColumns[rows])]
+        landcover[np.ix_(rows, columns)] = lookup[samples].T
+    return landcover
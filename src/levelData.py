@@ -0,0 +1,234 @@
+import os
+from datetime import datetime, timezone
+from nbt import nbt
+
+from .schematic import dataVersion
+
+spawnY = 2
+fixedTimes = {"noon": 6000, "midnight": 18000}
+
+
+def loadLevelData(mcWorldPath):
+    levelPath = mcWorldPath + "/level.dat"
+    if not os.path.exists(levelPath):
+        return None
+    return nbt.NBTFile(levelPath)
+
+
+def getSpawn(mcWorldPath):
+    levelData = loadLevelData(mcWorldPath)
+    if levelData is None:
+        return (0, 0)
+    return (levelData["Data"]["SpawnX"].value, levelData["Data"]["SpawnZ"].value)
+
+
+def setLevelNameSuffix(mcWorldPath, suffix):
+    levelData = loadLevelData(mcWorldPath)
+    if levelData is None:
+        return
+    levelName = levelData["Data"]["LevelName"].value
+    if suffix not in levelName:
+        levelData["Data"]["LevelName"].value = f"{levelName} {suffix}"
+        levelData.write_file(mcWorldPath + "/level.dat")
+
+
+def setGameRule(levelData, rule, value):
+    gameRules = levelData["Data"]["GameRules"]
+    if rule in gameRules:
+        gameRules[rule].value = value
+    else:
+        gameRules.tags.append(nbt.TAG_String(name=rule, value=value))
+
+
+def solarDayTime(longitude, now=None):
+    # Local solar time, Minecraft days start at 6:00 with tick 0
+    if now is None:
+        now = datetime.now(timezone.utc)
+    hours = (now.hour + now.minute / 60 + longitude / 15) % 24
+    return round((hours - 6) % 24 * 1000)
+
+
+def setWorldTime(mcWorldPath, mode, longitude):
+    levelData = loadLevelData(mcWorldPath)
+    if levelData is None:
+        return
+    if mode == "local":
+        if longitude is None:
+            print("Warning! The location is unknown, the world time is not changed")
+            return
+        dayTime = solarDayTime(longitude)
+    else:
+        dayTime = fixedTimes[mode]
+
+    levelData["Data"]["DayTime"].value = dayTime
+    setGameRule(levelData, "doDaylightCycle", "true" if mode == "local" else "false")
+    levelData.write_file(mcWorldPath + "/level.dat")
+    print(f"Set the world time to {mode} (tick {dayTime})")
+
+
+# Peaceful creative defaults of generated worlds, can be overridden by --gamerule
+defaultGameRules = {
+    "doMobSpawning": "false",
+    "doWeatherCycle": "false",
+    "doFireTick": "false",
+    "mobGriefing": "false",
+}
+
+# Superflat layers up to y=1, so the surroundings meet the generated ground
+flatLayers = [
+    ("minecraft:bedrock", 1),
+    ("minecraft:stone", 61),
+    ("minecraft:dirt", 3),
+    ("minecraft:grass_block", 1),
+]
+
+
+def compound(name, values):
+    tag = nbt.TAG_Compound(name=name)
+    for key, value in values.items():
+        if isinstance(value, nbt.TAG):
+            value.name = key
+            tag.tags.append(value)
+        elif isinstance(value, dict):
+            tag.tags.append(compound(key, value))
+        elif isinstance(value, bool):
+            tag.tags.append(nbt.TAG_Byte(name=key, value=int(value)))
+        elif isinstance(value, int):
+            tag.tags.append(nbt.TAG_Int(name=key, value=value))
+        else:
+            tag.tags.append(nbt.TAG_String(name=key, value=value))
+    return tag
+
+
+def compoundList(name, entries):
+    tag = nbt.TAG_List(name=name, type=nbt.TAG_Compound)
+    for entry in entries:
+        tag.tags.append(compound("", entry))
+    return tag
+
+
+def worldGenSettings():
+    layers = compoundList(
+        "layers", [{"block": block, "height": height} for block, height in flatLayers]
+    )
+    return {
+        "seed": nbt.TAG_Long(value=0),
+        "generate_features": False,
+        "bonus_chest": False,
+        "dimensions": {
+            "minecraft:overworld": {
+                "type": "minecraft:overworld",
+                "generator": {
+                    "type": "minecraft:flat",
+                    "settings": {
+                        "biome": "minecraft:plains",
+                        "features": False,
+                        "lakes": False,
+                        "layers": layers,
+                        "structure_overrides": nbt.TAG_List(type=nbt.TAG_String),
+                    },
+                },
+            },
+            "minecraft:the_nether": {
+                "type": "minecraft:the_nether",
+                "generator": {
+                    "type": "minecraft:noise",
+                    "settings": "minecraft:nether",
+                    "biome_source": {
+                        "type": "minecraft:multi_noise",
+                        "preset": "minecraft:nether",
+                    },
+                },
+            },
+            "minecraft:the_end": {
+                "type": "minecraft:the_end",
+                "generator": {
+                    "type": "minecraft:noise",
+                    "settings": "minecraft:end",
+                    "biome_source": {"type": "minecraft:the_end"},
+                },
+            },
+        },
+    }
+
+
+def createLevelData(mcWorldPath, levelName):
+    # Minimal level.dat of a creative superflat world, for output folders
+    # which are generated without a template world
+    os.makedirs(mcWorldPath + "/region", exist_ok=True)
+    if os.path.exists(mcWorldPath + "/level.dat"):
+        print("Using the existing level.dat")
+        return
+
+    data = {
+        "DataVersion": dataVersion,
+        "version": nbt.TAG_Int(value=19133),
+        "Version": {
+            "Id": dataVersion,
+            "Name": "1.19.2",
+            "Series": "main",
+            "Snapshot": False,
+        },
+        "LevelName": levelName,
+        "GameType": 1,
+        "Difficulty": nbt.TAG_Byte(value=0),
+        "allowCommands": True,
+        "hardcore": False,
+        "initialized": True,
+        "SpawnX": 0,
+        "SpawnY": spawnY,
+        "SpawnZ": 0,
+        "DayTime": nbt.TAG_Long(value=fixedTimes["noon"]),
+        "Time": nbt.TAG_Long(value=0),
+        "LastPlayed": nbt.TAG_Long(value=0),
+        "GameRules": {},
+        "WorldGenSettings": worldGenSettings(),
+    }
+    levelData = nbt.NBTFile()
+    levelData.name = ""
+    levelData.tags.append(compound("Data", data))
+    for rule, value in defaultGameRules.items():
+        setGameRule(levelData, rule, value)
+    levelData.write_file(mcWorldPath + "/level.dat")
+    print(f"Created a new world {levelName} at {mcWorldPath}")
+
+
+def setSpawn(mcWorldPath, spawnX, spawnZ):
+    levelData = loadLevelData(mcWorldPath)
+    if levelData is None:
+        return
+    levelData["Data"]["SpawnX"].value = spawnX
+    levelData["Data"]["SpawnY"].value = spawnY
+    levelData["Data"]["SpawnZ"].value = spawnZ
+    levelData.write_file(mcWorldPath + "/level.dat")
+
+
+def setGameRules(mcWorldPath, rules):
+    levelData = loadLevelData(mcWorldPath)
+    if levelData is None:
+        return
+    for rule in rules:
+        name, _, value = rule.partition("=")
+        setGameRule(levelData, name, value)
+    levelData.write_file(mcWorldPath + "/level.dat")
+
+
+def setDouble(levelData, name, value):
+    if name in levelData["Data"]:
+        levelData["Data"][name].value = value
+    else:
+        levelData["Data"].tags.append(nbt.TAG_Double(name=name, value=value))
+
+
+def setWorldBorder(mcWorldPath, sizeX, sizeZ):
+    # The border is a square, so it covers the longer side of the area
+    levelData = loadLevelData(mcWorldPath)
+    if levelData is None:
+        return
+    size = max(sizeX, sizeZ)
+    setDouble(levelData, "BorderCenterX", sizeX / 2)
+    setDouble(levelData, "BorderCenterZ", sizeZ / 2)
+    setDouble(levelData, "BorderSize", size)
+    setDouble(levelData, "BorderSizeLerpTarget", size)
+    levelData.write_file(mcWorldPath + "/level.dat")
+    print(f"Set the world border to {size} blocks around the generated area")
@@ -10,12 +10,65 @@ import time
 import gc
 import argparse
 import anvil
-from random import randint
+from random import Random, choice, randint, randrange, seed
 from math import floor
 import numpy as np
 
-from .getData import getData
+from .getData import defaultServers, getData, loadServerConfig
+from .bench import runBench
+from .demo import demoPath, getDemoData
+from .geocoding import confirmArea, geocode
+from .boundary import getBoundaryBbox, loadBoundary
 from .processData import processData
+from .intermediate import loadIntermediate, saveIntermediate
+from .audit import verifyOrderIndependence
+from .blockRecorder import BlockRecorder, recordedHeight
+from .schematic import saveSchematic
+from .objExport import saveObj
+from . import politeness
+from .manifest import hashData, writeManifest
+from .biomes import addBiomeChunks
+from .package import packageWorld
+from .tiles import splitIntoTiles
+from .memoryBudget import estimateMemory, formatGigabytes
+from .metrics import MetricsRecorder
+from .progress import Progress, StageTimer
+from .cancellation import Cancellation, confirmPartialSave
+from .checkpoint import Checkpoint
+from .tuning import (
+    availableMemory,
+    defaultDownloadWorkers,
+    downloadWorkers,
+    memoryBudget,
+)
+from .gltfExport import saveGltf
+from .rcon import streamToServer
+from .minecarts import railShape
+from .power import (
+    cableHeights,
+    cableSag,
+    minorPowerLineCode,
+    powerLineCode,
+    powerPoleCode,
+    powerTowerCode,
+)
+from .alignment import alignArea
+from .verification import verifyRegion
+from .pregen import writePregen
+from .buildingHeight import loadBuildingStyle
+from .levelData import (
+    createLevelData,
+    getSpawn,
+    setGameRules,
+    setLevelNameSuffix,
+    setSpawn,
+    setWorldBorder,
+    setWorldTime,
+)
+from .metadata import defaultAttribution, writeMetadata
+from .hooks import loadPlugins, runPostProcessingPasses
+from .optional import importOptional
+from .buildingAge import historic, modern, old
 
 parser = argparse.ArgumentParser(
     description="Arnis - Generate cities from real life in Minecraft using Python"
@@ -31,10 +84,425 @@ parser.add_argument(
     action="store_true",
     help="Enable debug mode",
 )
+parser.add_argument(
+    "--pregen",
+    dest="pregen",
+    choices=["forceload", "chunky"],
+    help="Write forceload tickets or Chunky commands to pre-render the area",
+)
+parser.add_argument(
+    "--markers",
+    dest="markers",
+    choices=["dynmap", "bluemap"],
+    help="Export named POIs, roads and areas as Dynmap or BlueMap markers",
+)
+parser.add_argument(
+    "--rescale-heights",
+    dest="rescaleHeights",
+    default=False,
+    action="store_true",
+    help="Compress tall buildings logarithmically instead of truncating them",
+)
+parser.add_argument(
+    "--cache-ttl",
+    dest="cacheTtl",
+    default=24,
+    type=float,
+    help="Hours to reuse a cached Overpass response or Geofabrik extract",
+)
+parser.add_argument(
+    "--no-cache",
+    dest="noCache",
+    default=False,
+    action="store_true",
+    help="Always download fresh data instead of using the response cache",
+)
+parser.add_argument(
+    "--building-style",
+    dest="buildingStyle",
+    default="default",
+    help="Default building heights: default, europe, us or a path to a JSON file",
+)
+parser.add_argument(
+    "--boundary",
+    dest="boundary",
+    help="GeoJSON polygon or JSON list of [lat, lng] vertices to clip the world to",
+)
+parser.add_argument(
+    "--new-world",
+    dest="newWorld",
+    default=False,
+    action="store_true",
+    help="Create a creative superflat world at --path instead of using an existing one",
+)
+parser.add_argument(
+    "--spawn",
+    dest="spawn",
+    nargs=2,
+    type=int,
+    metavar=("X", "Z"),
+    help="Spawn point of a new world (default: center of the generated area)",
+)
+parser.add_argument(
+    "--gamerule",
+    dest="gamerules",
+    action="append",
+    default=[],
+    metavar="NAME=VALUE",
+    help="Set a gamerule in level.dat, can be given multiple times",
+)
+parser.add_argument(
+    "--world-border",
+    dest="worldBorder",
+    default=False,
+    action="store_true",
+    help="Limit the world to the generated area with a world border",
+)
+parser.add_argument(
+    "--spawn-platform",
+    dest="spawnPlatform",
+    default=False,
+    action="store_true",
+    help="Build a small platform at spawn if it would be in water",
+)
+parser.add_argument(
+    "--attribution",
+    dest="attribution",
+    default=defaultAttribution,
+    help="OSM attribution text written to level.dat and metadata.json",
+)
+parser.add_argument(
+    "--geofabrik",
+    dest="geofabrik",
+    help="Geofabrik region to download instead of querying Overpass, needs --boundary",
+)
+parser.add_argument(
+    "--building-heights-source",
+    dest="buildingHeightsSource",
+    help="GeoJSON building footprints (e.g. Overture or Microsoft) to fill in heights",
+)
+parser.add_argument(
+    "--fill-budget",
+    dest="fillBudget",
+    type=float,
+    help="Total seconds available for flood filling areas (default: 7 per area), "
+    + "also used by the areas the scanline fill hands on to the flood fill",
+)
+parser.add_argument(
+    "--fill-algorithm",
+    dest="fillAlgorithm",
+    default="scanline",
+    choices=["scanline", "floodfill"],
+    help="Fill areas line by line (default) or with the previous flood fill",
+)
+parser.add_argument(
+    "--overture",
+    dest="overture",
+    nargs="+",
+    help="Overture Maps GeoParquet files (buildings, segments, places) instead of OSM",
+)
+parser.add_argument(
+    "--plugin",
+    dest="plugins",
+    action="append",
+    default=[],
+    help="Python module registering post-processing passes, can be repeated",
+)
+parser.add_argument(
+    "--offline-demo",
+    dest="offlineDemo",
+    nargs="?",
+    const=demoPath,
+    metavar="FILE",
+    help="Generate a small bundled sample area without any network access, "
+    + "or the Overpass JSON in FILE",
+)
+parser.add_argument(
+    "--bench",
+    dest="bench",
+    nargs="*",
+    metavar="FILE",
+    help="Generate the bundled sample area or the given Overpass JSON files "
+    + "into temporary worlds and report the duration of every stage",
+)
+parser.add_argument(
+    "--proxy",
+    dest="proxy",
+    help="HTTP proxy for Overpass and Geofabrik downloads, e.g. http://host:3128",
+)
+parser.add_argument(
+    "--servers",
+    dest="servers",
+    help="JSON file with a custom Overpass server list and proxy",
+)
+parser.add_argument(
+    "--landcover",
+    dest="landcover",
+    nargs="+",
+    help="ESA WorldCover tiles to fill in ground without any OSM landuse",
+)
+parser.add_argument(
+    "--imagery",
+    dest="imagery",
+    help="XYZ orthophoto tile URL, e.g. https://host/{z}/{x}/{y}.jpg, to tint ground",
+)
+parser.add_argument(
+    "--imagery-zoom",
+    dest="imageryZoom",
+    default=17,
+    type=int,
+    help="Zoom level of the orthophoto tiles (default: 17)",
+)
+parser.add_argument(
+    "--gpx",
+    dest="gpx",
+    nargs="+",
+    help="GPX files whose tracks and waypoints are drawn as paths into the world",
+)
+parser.add_argument(
+    "--promenade",
+    dest="promenade",
+    default=False,
+    action="store_true",
+    help="Pave built-up ground along water as a promenade with railing and benches",
+)
+parser.add_argument(
+    "--polite",
+    dest="polite",
+    default=False,
+    action="store_true",
+    help="Download one file at a time with long pauses, for frequent generations",
+)
+parser.add_argument(
+    "--contact",
+    dest="contact",
+    help="E-mail or URL added to the User-Agent of all requests",
+)
+parser.add_argument(
+    "--download-workers",
+    dest="downloadWorkers",
+    type=int,
+    help="Number of parallel tile downloads (default: twice the CPU cores)",
+)
+parser.add_argument(
+    "--no-auto-tune",
+    dest="autoTune",
+    default=True,
+    action="store_false",
+    help="Don't choose download workers and the memory budget from this machine",
+)
+parser.add_argument(
+    "--yes",
+    dest="yes",
+    default=False,
+    action="store_true",
+    help="Generate the looked up area without asking for confirmation",
+)
+parser.add_argument(
+    "--ferries",
+    dest="ferries",
+    choices=["buoys", "ice"],
+    help="Mark ferry routes across water with buoys or as packed ice lanes",
+)
+parser.add_argument(
+    "--save-intermediate",
+    dest="saveIntermediate",
+    help="Save the processed data to a .npz file to reuse it with other options",
+)
+parser.add_argument(
+    "--load-intermediate",
+    dest="loadIntermediate",
+    help="Generate the world from processed data saved with --save-intermediate",
+)
+parser.add_argument(
+    "--verify-order",
+    dest="verifyOrder",
+    default=False,
+    action="store_true",
+    help="Check that shuffling the elements doesn't change the result, then exit",
+)
+parser.add_argument(
+    "--schematic",
+    dest="schematic",
+    help="Also write the generated blocks to a Sponge .schem file for WorldEdit",
+)
+parser.add_argument(
+    "--align",
+    dest="align",
+    choices=["chunk", "region"],
+    help="Snap the edges of the generated area to chunk or region boundaries",
+)
+parser.add_argument(
+    "--align-mode",
+    dest="alignMode",
+    choices=["outward", "inward"],
+    default="outward",
+    help="Grow the area with ground (default) or crop it to the boundaries",
+)
+parser.add_argument(
+    "--verify-save",
+    dest="verifySave",
+    default=False,
+    action="store_true",
+    help="Read every saved chunk back and save corrupted regions again",
+)
+parser.add_argument(
+    "--obj",
+    dest="obj",
+    help="Also export the generated blocks as an OBJ model, e.g. for Blender",
+)
+parser.add_argument(
+    "--teleports",
+    dest="teleports",
+    default=False,
+    action="store_true",
+    help="Add a datapack with teleport functions and a warp book for named places",
+)
+parser.add_argument(
+    "--rcon",
+    dest="rcon",
+    help="Also stream the blocks to a running server via RCON, e.g. localhost:25575",
+)
+parser.add_argument(
+    "--rcon-rate",
+    dest="rconRate",
+    default=100,
+    type=float,
+    help="RCON commands sent per second (default: 100)",
+)
+parser.add_argument(
+    "--minecarts",
+    dest="minecarts",
+    default=False,
+    action="store_true",
+    help="Lay working minecart tracks with stops at platforms along railways",
+)
+parser.add_argument(
+    "--world-time",
+    dest="worldTime",
+    choices=["local", "noon", "midnight"],
+    help="Start at the current local time of the area, or always at noon/midnight",
+)
+parser.add_argument(
+    "--export-rasters",
+    dest="exportRasters",
+    help="Path prefix for a georeferenced landuse map and heightmap (PNG)",
+)
+parser.add_argument(
+    "--tiles",
+    dest="tiles",
+    type=int,
+    metavar="REGIONS",
+    help="Split the world into separate worlds of REGIONS x REGIONS region files",
+)
+parser.add_argument(
+    "--package",
+    dest="package",
+    choices=["zip"],
+    help="Also pack the finished world into a zip archive next to the world folder",
+)
+parser.add_argument(
+    "--compression-level",
+    dest="compressionLevel",
+    type=int,
+    choices=range(10),
+    metavar="0-9",
+    help="Deflate level of the --package archive, 0 stores the files uncompressed",
+)
+parser.add_argument(
+    "--stream-save",
+    dest="streamSave",
+    default=False,
+    action="store_true",
+    help="Save regions as soon as they are generated to reduce the memory usage",
+)
+parser.add_argument(
+    "--max-memory",
+    dest="maxMemory",
+    type=float,
+    metavar="GB",
+    help="Save regions early or stop if the world would need more memory",
+)
+parser.add_argument(
+    "--metrics-out",
+    dest="metricsOut",
+    help="Write the time and blocks per kind of element to a JSON file",
+)
+parser.add_argument(
+    "--checkpoint",
+    dest="checkpoint",
+    metavar="DIR",
+    help="Directory for intermediate results to resume a crashed or cancelled run",
+)
+parser.add_argument(
+    "--biomes",
+    dest="biomes",
+    default=False,
+    action="store_true",
+    help="Set forest, swamp, beach, river and ocean biomes from the landuse",
+)
+parser.add_argument(
+    "--seed",
+    dest="seed",
+    type=int,
+    help="Seed for the random details like trees, to generate a world identically",
+)
+parser.add_argument(
+    "--gltf",
+    dest="gltf",
+    help="Also export the generated blocks as a glTF model for browsers and Blender",
+)
 args = parser.parse_args()
-if args.city is None or args.state is None or args.country is None or args.path is None:
+politeness.configure(args.polite, args.contact)
+if args.polite:
+    args.downloadWorkers = 1
+# Options given on the command line always win over the tuned values
+autoMemoryBudget = None
+if args.downloadWorkers is None:
+    args.downloadWorkers = defaultDownloadWorkers
+    if args.autoTune:
+        args.downloadWorkers = downloadWorkers(os.cpu_count())
+if args.maxMemory is None and args.autoTune:
+    autoMemoryBudget = memoryBudget(availableMemory())
+if args.bench is not None:
+    runBench(args.bench, args.metricsOut)
+    os._exit(0)
+if args.offlineDemo or args.loadIntermediate is not None:
+    if args.path is None:
+        print("Error! Missing arguments")
+        os._exit(1)
+elif args.geofabrik is not None:
+    if args.boundary is None or args.path is None:
+        print("Error! Missing arguments, --geofabrik requires --boundary and --path")
+        os._exit(1)
+elif args.overture is not None:
+    if args.path is None:
+        print("Error! Missing arguments")
+        os._exit(1)
+elif (
+    args.city is None or args.state is None or args.country is None or args.path is None
+):
     print("Error! Missing arguments")
     os._exit(1)
+if args.loadIntermediate is not None:
+    # Written while processing the OSM data, which isn't kept in the file
+    processingOutputs = {
+        "--markers": args.markers,
+        "--teleports": args.teleports,
+        "--export-rasters": args.exportRasters,
+    }
+    for option, value in processingOutputs.items():
+        if value:
+            print(f"Error! {option} can't be combined with --load-intermediate")
+            os._exit(1)
+if args.verifyOrder and args.loadIntermediate is not None:
+    print("Error! --verify-order needs the OSM data, not --load-intermediate")
+    os._exit(1)
+if args.streamSave and (args.spawnPlatform or args.plugins):
+    # Both set blocks anywhere after the regions have been saved
+    print("Error! --stream-save can't be combined with --spawn-platform or --plugin")
+    os._exit(1)
+loadPlugins(args.plugins)
 
 gc.collect()
 np.seterr(all="raise")
@@ -71,32 +539,247 @@ white_concrete = anvil.Block("minecraft", "white_concrete")
 black_concrete = anvil.Block("minecraft", "black_concrete")
 gray_concrete = anvil.Block("minecraft", "gray_concrete")
 light_gray_concrete = anvil.Block("minecraft", "light_gray_concrete")
+yellow_concrete = anvil.Block("minecraft", "yellow_concrete")
+light_gray_stained_glass = anvil.Block("minecraft", "light_gray_stained_glass")
 green_stained_hardened_clay = anvil.Block.from_numeric_id(159, 5)
 dirt = anvil.Block("minecraft", "dirt")
 glowstone = anvil.Block("minecraft", "glowstone")
 sponge = anvil.Block("minecraft", "sponge")
+oak_planks = anvil.Block("minecraft", "oak_planks")
+oak_fence = anvil.Block("minecraft", "oak_fence")
+mossy_cobblestone = anvil.Block("minecraft", "mossy_cobblestone")
+mossy_stone_bricks = anvil.Block("minecraft", "mossy_stone_bricks")
+cracked_stone_bricks = anvil.Block("minecraft", "cracked_stone_bricks")
+stone_bricks = anvil.Block("minecraft", "stone_bricks")
+bricks = anvil.Block("minecraft", "bricks")
+quartz_block = anvil.Block("minecraft", "quartz_block")
+dirt_path = anvil.Block("minecraft", "dirt_path")
+lantern = anvil.Block("minecraft", "lantern")
+oak_stairs = anvil.Block("minecraft", "oak_stairs")
+iron_bars = anvil.Block("minecraft", "iron_bars")
+andesite = anvil.Block("minecraft", "andesite")
+ladder = anvil.Block("minecraft", "ladder", properties={"facing": "south"})
+packed_ice = anvil.Block("minecraft", "packed_ice")
+sea_lantern = anvil.Block("minecraft", "sea_lantern")
+gravel = anvil.Block("minecraft", "gravel")
+redstone_block = anvil.Block("minecraft", "redstone_block")
+stone_button = anvil.Block("minecraft", "stone_button", properties={"face": "floor"})
+tintedGround = [
+    anvil.Block("minecraft", "white_terracotta"),
+    anvil.Block("minecraft", "light_gray_terracotta"),
+    anvil.Block("minecraft", "brown_terracotta"),
+    anvil.Block("minecraft", "green_terracotta"),
+    anvil.Block("minecraft", "terracotta"),
+    gray_concrete,
+    light_gray_concrete,
+    white_concrete,
+    anvil.Block("minecraft", "lime_terracotta"),
+]
 
+recorder = None
+metrics = None
+stages = StageTimer()
+checkpoint = None
+# Created on demand, so only regions with generated chunks are saved
 regions = {}
-for x in range(0, 3):
-    for z in range(0, 3):
-        regions["r." + str(x) + "." + str(z)] = anvil.EmptyRegion(0, 0)
+savedRegions = []
+# Saved before a resumed run, their blocks are generated again and dropped
+resumedRegions = set()
+# Blocks are set up to a few blocks next to the current row, e.g. for bridges
+streamMargin = 16
+
+
+def getRegion(regionX, regionZ):
+    # None for regions saved by a previous run, which are complete already
+    identifier = "r." + str(regionX) + "." + str(regionZ)
+    if identifier not in regions:
+        if identifier in resumedRegions:
+            return None
+        if identifier in savedRegions:
+            print(f"Error! {identifier} has already been saved")
+            os._exit(1)
+        regions[identifier] = anvil.EmptyRegion(0, 0)
+    return regions[identifier]
 
 
 def setBlock(block, x, y, z):
     flooredX = floor(x / 512)
     flooredZ = floor(z / 512)
-    identifier = "r." + str(flooredX) + "." + str(flooredZ)
-    if identifier not in regions:
-        regions[identifier] = anvil.EmptyRegion(0, 0)
-    regions[identifier].set_block(block, x - flooredX * 512, y, z - flooredZ * 512)
+    region = getRegion(flooredX, flooredZ)
+    if region is None:
+        return
+    region.set_block(block, x - flooredX * 512, y, z - flooredZ * 512)
+    if recorder is not None:
+        recorder.setBlock(block, x, y, z)
+    if metrics is not None:
+        metrics.countBlock()
 
 
 def fillBlocks(block, x1, y1, z1, x2, y2, z2):
-    for x in range(x1, x2 + 1):
-        for y in range(y1, y2 + 1):
-            for z in range(z1, z2 + 1):
-                if not (x == x2 + 1 or y == y2 + 1 or z == z2 + 1):
-                    setBlock(block, x, y, z)
+    # Looks up every region once instead of once per block
+    for regionX in range(floor(x1 / 512), floor(x2 / 512) + 1):
+        for regionZ in range(floor(z1 / 512), floor(z2 / 512) + 1):
+            region = getRegion(regionX, regionZ)
+            if region is None:
+                continue
+            setRegionBlock = region.set_block
+            offsetX = regionX * 512
+            offsetZ = regionZ * 512
+            for x in range(max(x1, offsetX), min(x2, offsetX + 511) + 1):
+                for z in range(max(z1, offsetZ), min(z2, offsetZ + 511) + 1):
+                    for y in range(y1, y2 + 1):
+                        setRegionBlock(block, x - offsetX, y, z - offsetZ)
+    if recorder is not None:
+        recorder.fill(block, x1, y1, z1, x2, y2, z2)
+    if metrics is not None and x1 <= x2 and y1 <= y2 and z1 <= z2:
+        metrics.countBlock((x2 - x1 + 1) * (y2 - y1 + 1) * (z2 - z1 + 1))
+
+
+def wallBlock(age):
+    if age == historic:
+        return choice([mossy_cobblestone, mossy_stone_bricks, cracked_stone_bricks])
+    elif age == old:
+        return choice([stone_bricks, bricks])
+    elif age == modern:
+        return quartz_block
+    return white_concrete
+
+
+def buildBoulder(x, z):
+    # Seeded by the position, so regenerating an area gives the same rocks
+    rng = Random(x * 100003 + z)
+    size = rng.randint(2, 4)
+    for dx in range(size):
+        for dz in range(size):
+            height = rng.randint(1, size - 1)
+            for y in range(2, 2 + height):
+                setBlock(rng.choice([stone, andesite]), x + dx, y, z + dz)
+
+
+def buildLookout(x, z):
+    # Wooden platform on posts, climbed by a ladder on its south side
+    for postX, postZ in ((-2, -2), (-2, 2), (2, -2), (2, 2), (0, 2)):
+        fillBlocks(log, x + postX, 2, z + postZ, x + postX, 5, z + postZ)
+    fillBlocks(oak_planks, x - 2, 6, z - 2, x + 2, 6, z + 2)
+    for offset in range(-2, 3):
+        setBlock(oak_fence, x + offset, 7, z - 2)
+        setBlock(oak_fence, x - 2, 7, z + offset)
+        setBlock(oak_fence, x + 2, 7, z + offset)
+        if offset != 0:
+            setBlock(oak_fence, x + offset, 7, z + 2)
+    fillBlocks(ladder, x, 2, z + 3, x, 6, z + 3)
+    setBlock(oak_fence, x, 7, z)
+    setBlock(lantern, x, 8, z)
+
+
+def buildTrack(imgarray, x, z, station):
+    def isTrack(neighbourX, neighbourZ):
+        return (
+            0 <= neighbourX < len(imgarray)
+            and 0 <= neighbourZ < len(imgarray[0])
+            and imgarray[neighbourX][neighbourZ] in (90, 91)
+        )
+
+    shape = railShape(
+        isTrack(x, z - 1), isTrack(x, z + 1), isTrack(x + 1, z), isTrack(x - 1, z)
+    )
+    straight = shape in ("north_south", "east_west")
+    if station and straight:
+        # Unpowered, so carts stop until the button next to the track is pressed
+        setBlock(stone, x, 1, z)
+        setBlock(
+            anvil.Block(
+                "minecraft", "powered_rail", {"shape": shape, "powered": "false"}
+            ),
+            x,
+            2,
+            z,
+        )
+        # On the side which is already generated, so nothing overwrites it
+        sideX, sideZ = (x, z - 1) if shape == "east_west" else (x - 1, z)
+        if not isTrack(sideX, sideZ):
+            setBlock(stone_button, sideX, 2, sideZ)
+    elif straight and (x + z) % 8 == 0:
+        setBlock(redstone_block, x, 1, z)
+        setBlock(
+            anvil.Block(
+                "minecraft", "powered_rail", {"shape": shape, "powered": "true"}
+            ),
+            x,
+            2,
+            z,
+        )
+    else:
+        setBlock(gravel, x, 1, z)
+        setBlock(anvil.Block("minecraft", "rail", {"shape": shape}), x, 2, z)
+
+
+def buildPylon(x, z, tower):
+    height = cableHeights[powerLineCode if tower else minorPowerLineCode] + 1
+    if not tower:
+        fillBlocks(log, x, 2, z, x, height, z)
+        return
+    # Lattice legs joining into a mast, with cross arms carrying the cables
+    for legX, legZ in ((-1, -1), (-1, 1), (1, -1), (1, 1)):
+        fillBlocks(iron_bars, x + legX, 2, z + legZ, x + legX, 5, z + legZ)
+    fillBlocks(iron_bars, x, 6, z, x, height, z)
+    fillBlocks(iron_bars, x - 2, height - 1, z, x + 2, height - 1, z)
+    fillBlocks(iron_bars, x, height - 1, z - 2, x, height - 1, z + 2)
+
+
+def buildCable(imgarray, x, z, cableCode):
+    powerCodes = (powerTowerCode, powerPoleCode, powerLineCode, minorPowerLineCode)
+
+    def isPower(neighbourX, neighbourZ):
+        return (
+            0 <= neighbourX < len(imgarray)
+            and 0 <= neighbourZ < len(imgarray[0])
+            and imgarray[neighbourX][neighbourZ] in powerCodes
+        )
+
+    # The nearest pylon within the radius decides how far the cable sags
+    radius = 24
+    window = imgarray[
+        max(x - radius, 0) : x + radius + 1, max(z - radius, 0) : z + radius + 1
+    ]
+    pylons = np.argwhere(np.isin(window, (powerTowerCode, powerPoleCode)))[:, :2]
+    distance = radius
+    if len(pylons) > 0:
+        offsets = pylons - (min(x, radius), min(z, radius))
+        distance = int(np.abs(offsets).max(axis=1).min())
+    axis = "x" if isPower(x - 1, z) or isPower(x + 1, z) else "z"
+    setBlock(
+        anvil.Block("minecraft", "chain", {"axis": axis}),
+        x,
+        cableHeights[cableCode] - cableSag(distance, cableCode),
+        z,
+    )
+
+
+def isInterior(imgarray, x, z):
+    if not (0 <= x < len(imgarray) and 0 <= z < len(imgarray[0])):
+        return False
+    return 70 <= imgarray[x][z] <= 79
+
+
+def isPartyWall(imgarray, x, z):
+    # A wall shared by two buildings has an interior on both sides, either right
+    # next to it or behind the wall of the neighbouring building
+    for dx, dz in ((1, 0), (0, 1)):
+        for side in (1, -1):
+            if not isInterior(imgarray, x - side * dx, z - side * dz):
+                continue
+            nextX, nextZ = x + side * dx, z + side * dz
+            if isInterior(imgarray, nextX, nextZ):
+                return True
+            if (
+                0 <= nextX < len(imgarray)
+                and 0 <= nextZ < len(imgarray[0])
+                and 60 <= imgarray[nextX][nextZ] <= 69
+                and isInterior(imgarray, nextX + side * dx, nextZ + side * dz)
+            ):
+                return True
+    return False
 
 
 mcWorldPath = args.path
@@ -114,33 +797,263 @@ def saveRegion(region="all"):
         print(f"Saved {region}")
 
 
+def flushRegionColumn(regionX):
+    # Saves and frees the regions which the generation has moved past
+    for key in [key for key in regions if key.split(".")[1] == str(regionX)]:
+        saveRegion(key)
+        savedRegions.append(key)
+        del regions[key]
+    if checkpoint is not None:
+        checkpoint.saveRegions(savedRegions)
+
+
+def checkMemoryBudget(imgarray, recording, budgetGigabytes, strict):
+    # Switches to streaming or stops before running out of memory halfway.
+    # Budgets tuned from the free memory only warn, the estimate is rough.
+    budget = budgetGigabytes * 1024**3
+    height = recordedHeight if recording else 0
+    estimate = estimateMemory(len(imgarray), len(imgarray[0]), args.streamSave, height)
+    if estimate <= budget:
+        return
+    streamed = estimateMemory(len(imgarray), len(imgarray[0]), True, height)
+    problem = None
+    if args.streamSave or streamed > budget:
+        problem = f"more than the memory budget of {budgetGigabytes} GB"
+    elif args.spawnPlatform or args.plugins:
+        problem = "remove --spawn-platform and --plugin to save regions early"
+    if problem is not None:
+        if not strict:
+            print(
+                f"Warning! The area needs about {formatGigabytes(estimate)} "
+                + "of memory, more than is available"
+            )
+            return
+        print(
+            f"Error! The area needs about {formatGigabytes(estimate)} of memory, "
+            + problem
+        )
+        os._exit(1)
+    print(
+        f"The area needs about {formatGigabytes(estimate)} of memory, "
+        + "saving regions during the generation"
+    )
+    args.streamSave = True
+
+
+def resumeGeneration(keys):
+    # Continues right before the first unsaved region column, so the blocks
+    # which the previous rows set into it are generated again
+    resumedRegions.update(keys)
+    savedRegions.extend(keys)
+    for key in keys:
+        regions.pop(key, None)
+    lastColumn = max(int(key.split(".")[1]) for key in keys)
+    startRow = max(0, (lastColumn + 1) * 512 - streamMargin)
+    print(f"Resuming the generation at row {startRow} ({len(keys)} regions saved)")
+    return startRow
+
+
+def verifySave():
+    print("Verifying saved regions...")
+    for key in savedRegions + list(regions):
+        path = mcWorldPath + "/region/" + key + ".mca"
+        failed = verifyRegion(path)
+        if failed == []:
+            continue
+        if key not in regions:
+            print(f"Error! {key} is corrupted ({describeFailure(failed)})")
+            continue
+
+        print(f"Warning! {key} is corrupted ({describeFailure(failed)}), saving again")
+        saveRegion(key)
+        failed = verifyRegion(path)
+        if failed != []:
+            print(f"Error! {key} is still corrupted ({describeFailure(failed)})")
+
+
+def describeFailure(failed):
+    if failed is None:
+        return "unreadable file"
+    return f"{len(failed)} unreadable chunks"
+
+
+def buildSpawnPlatform(imgarray):
+    spawnX, spawnZ = getSpawn(mcWorldPath)
+    if not (0 <= spawnX < len(imgarray) and 0 <= spawnZ < len(imgarray[0])):
+        return
+    if imgarray[spawnX][spawnZ] not in (35, 37, 38):  # Wetland, pool, water
+        return
+
+    print(f"Building spawn platform at {spawnX}, {spawnZ}")
+    fillBlocks(oak_planks, spawnX - 3, 2, spawnZ - 3, spawnX + 3, 2, spawnZ + 3)
+    fillBlocks(air, spawnX - 3, 3, spawnZ - 3, spawnX + 3, 4, spawnZ + 3)
+    for offset in range(-3, 4):
+        setBlock(oak_fence, spawnX + offset, 3, spawnZ + 3)
+        setBlock(oak_fence, spawnX - 3, 3, spawnZ + offset)
+        setBlock(oak_fence, spawnX + 3, 3, spawnZ + offset)
+        # Leave the north side open as a dock, marked by a line pointing north
+        if offset != 0:
+            setBlock(oak_fence, spawnX + offset, 3, spawnZ - 3)
+    fillBlocks(gray_concrete, spawnX, 2, spawnZ - 3, spawnX, 2, spawnZ)
+
+
+def centerLongitude(rawdata):
+    longitudes = [
+        element["lon"] for element in rawdata["elements"] if element["type"] == "node"
+    ]
+    if len(longitudes) == 0:
+        return None
+    return (min(longitudes) + max(longitudes)) / 2
+
+
+def getServers():
+    servers, proxy = defaultServers, None
+    if args.servers is not None:
+        servers, proxy = loadServerConfig(args.servers)
+    if args.proxy is not None:
+        proxy = args.proxy
+    return servers, proxy
+
+
+def getRawData():
+    cacheTtl = 0 if args.noCache else args.cacheTtl
+    servers, proxy = getServers()
+    if args.offlineDemo:
+        return getDemoData(args.offlineDemo)
+    elif args.overture is not None:
+        overture = importOptional(".overture", "--overture", "pyarrow")
+        return overture.getOvertureData(args.overture)
+    elif args.geofabrik is not None:
+        geofabrik = importOptional(".geofabrik", "--geofabrik", "osmium")
+        return geofabrik.getGeofabrikData(
+            args.geofabrik,
+            getBoundaryBbox(loadBoundary(args.boundary)),
+            cacheTtl,
+            proxy,
+        )
+    else:
+        name, bbox = geocode(args.city, args.state, args.country, proxy)
+        if not args.yes and not confirmArea(name, bbox):
+            print("Aborted")
+            os._exit(0)
+
+        return getData(bbox, args.debug, cacheTtl, servers, proxy)
+
+
+def startStage(name):
+    stages.startStage(name)
+
+
+def countMetric(name, amount):
+    if metrics is not None:
+        metrics.count(name, amount)
+
+
 def run():
+    global recorder, metrics, checkpoint
+    if args.metricsOut is not None:
+        metrics = MetricsRecorder()
+    if args.checkpoint is not None:
+        checkpoint = Checkpoint(args.checkpoint, vars(args))
+    if args.newWorld:
+        createLevelData(mcWorldPath, args.city or os.path.basename(mcWorldPath))
     if not (os.path.exists(mcWorldPath + "/region")):
         print("Error! No Minecraft world found at given path")
         os._exit(1)
 
-    rawdata = getData(args.city, args.state, args.country, args.debug)
-    imgarray = processData(rawdata, args)
+    longitude = None
+    dataHash = None
+    if args.loadIntermediate is not None:
+        startStage("load")
+        imgarray, imgAge = loadIntermediate(args.loadIntermediate)
+    else:
+        startStage("fetch")
+        rawdata = None if checkpoint is None else checkpoint.loadRawData()
+        if rawdata is None:
+            rawdata = getRawData()
+            if checkpoint is not None:
+                checkpoint.saveRawData(rawdata)
+        if args.verifyOrder:
+            verifyOrderIndependence(rawdata, args)
+        countMetric("elements", len(rawdata["elements"]))
+        dataHash = hashData(rawdata)
+        longitude = centerLongitude(rawdata)
+        startStage("process")
+        processed = None if checkpoint is None else checkpoint.loadProcessed()
+        if processed is None:
+            processed = processData(rawdata, args)
+            if checkpoint is not None:
+                checkpoint.saveProcessed(*processed)
+        imgarray, imgAge = processed
+        if args.saveIntermediate is not None:
+            saveIntermediate(args.saveIntermediate, imgarray, imgAge)
+
+    alignment = None
+    if args.align is not None:
+        imgarray, imgAge, alignment = alignArea(
+            imgarray, imgAge, args.align, args.alignMode
+        )
+
+    if args.newWorld:
+        spawn = args.spawn or (len(imgarray) // 2, len(imgarray[0]) // 2)
+        setSpawn(mcWorldPath, *spawn)
+    if args.gamerules:
+        setGameRules(mcWorldPath, args.gamerules)
 
+    recording = any(
+        output is not None
+        for output in (args.schematic, args.obj, args.gltf, args.rcon)
+    )
+    if args.maxMemory is not None:
+        checkMemoryBudget(imgarray, recording, args.maxMemory, True)
+    elif autoMemoryBudget is not None:
+        checkMemoryBudget(imgarray, recording, autoMemoryBudget, False)
+    if recording:
+        recorder = BlockRecorder(len(imgarray), recordedHeight, len(imgarray[0]))
+
+    mullionSpacing = loadBuildingStyle(args.buildingStyle)["curtainWall"][
+        "mullionSpacing"
+    ]
+
+    if args.biomes:
+        addBiomeChunks(regions, imgarray)
+
+    # Seeded right before generating, so the same seed always gives the same world
+    generationSeed = args.seed if args.seed is not None else randrange(2**32)
+    if checkpoint is not None:
+        generationSeed = checkpoint.generationSeed(generationSeed)
+    seed(generationSeed)
+
+    startStage("generate")
+    countMetric("pixels", len(imgarray) * len(imgarray[0]))
     print("Generating minecraft world...")
 
     x = 0
+    if checkpoint is not None and checkpoint.savedRegions():
+        x = resumeGeneration(checkpoint.savedRegions())
     z = 0
     doorIncrement = 0
-    ElementIncr = 0
-    ElementsLen = len(imgarray)
-    lastProgressPercentage = 0
-    for i in imgarray:
-        progressPercentage = round(100 * (ElementIncr + 1) / ElementsLen)
-        if (
-            progressPercentage % 10 == 0
-            and progressPercentage != lastProgressPercentage
-        ):
-            print(f"Pixel {ElementIncr + 1}/{ElementsLen} ({progressPercentage}%)")
-            lastProgressPercentage = progressPercentage
+    ElementIncr = x
+    progress = Progress("Pixel", len(imgarray))
+    cancellation = Cancellation()
+    cancellation.install()
+    for i in imgarray[x:]:
+        if cancellation.requested:
+            break
+        progress.update(ElementIncr + 1)
+        # Reseeded on every row a resumed run can start at, so it continues
+        # with the same random details as an uninterrupted run
+        if (x + streamMargin) % 512 == 0:
+            seed(generationSeed + x)
 
         z = 0
         for j in i:
+            if j == 1:  # Outside of the boundary
+                z += 1
+                continue
+
+            if metrics is not None:
+                metrics.startElement(j)
             setBlock(dirt, x, 0, z)
             if j == 0:  # Ground
                 setBlock(light_gray_concrete, x, 1, z)
@@ -160,11 +1073,51 @@ def run():
                 setBlock(light_gray_concrete, x - 1, 2, z + 1)
             elif j == 14:  # Railway
                 setBlock(iron_block, x, 2, z)
+            elif j == 15:  # GPX track
+                setBlock(dirt_path, x, 1, z)
+                setBlock(air, x, 2, z)
+            elif j == 16:  # GPX waypoint
+                setBlock(dirt_path, x, 1, z)
+                setBlock(oak_fence, x, 2, z)
+                setBlock(lantern, x, 3, z)
+            elif j == 17:  # Promenade
+                setBlock(stone_bricks, x, 1, z)
+                if randint(0, 60) == 0:
+                    setBlock(oak_stairs, x, 2, z)
+            elif j == 18:  # Promenade railing
+                setBlock(stone_bricks, x, 1, z)
+                # Leave regular gaps to get into the water
+                if (x + z) % 16 != 0:
+                    setBlock(iron_bars, x, 2, z)
+            elif j == 19:  # Street markings
+                setBlock(white_concrete, x, 1, z)
+                setBlock(air, x, 2, z)
             elif j == 20:  # Parking
                 setBlock(gray_concrete, x, 1, z)
             elif j == 21:  # Fountain border
                 setBlock(light_gray_concrete, x, 2, z)
                 setBlock(white_concrete, x, 1, z)
+            elif j == 25:  # Boulder
+                setBlock(stone, x, 1, z)
+                buildBoulder(x, z)
+            elif j == 26:  # Bare rock
+                setBlock(stone, x, 1, z)
+                if Random(x * 100003 + z).randint(0, 150) == 0:
+                    buildBoulder(x, z)
+            elif j == 27:  # Viewpoint
+                setBlock(grass_block, x, 1, z)
+                buildLookout(x, z)
+            elif j == 29:  # Street lamp
+                setBlock(light_gray_concrete, x, 1, z)
+                fillBlocks(cobblestone_wall, x, 2, z, x, 4, z)
+                setBlock(lantern, x, 5, z)
+            elif j == 28:  # Ferry lane
+                if args.ferries == "ice":
+                    setBlock(packed_ice, x, 1, z)
+                elif (x + z) % 8 == 0:
+                    setBlock(sea_lantern, x, 1, z)
+                else:
+                    setBlock(water, x, 1, z)
             elif j >= 22 and j <= 24:  # Fence
                 if str(j)[-1] == "2" or int(str(j[0])[-1]) == 2:
                     setBlock(cobblestone_wall, x, 2, z)
@@ -239,6 +1192,11 @@ def run():
                 setBlock(water, x, 1, z)
             elif j == 39:  # Raw grass
                 setBlock(grass_block, x, 1, z)
+            elif j == 40:  # Ocean
+                setBlock(water, x, 1, z)
+                setBlock(sand, x, 0, z)
+            elif j >= 41 and j <= 49:  # Tinted ground
+                setBlock(tintedGround[int(j) - 41], x, 1, z)
             elif j >= 50 and j <= 59:  # House corner
                 building_height = 5
                 if j == 51:
@@ -260,7 +1218,7 @@ def run():
                 elif j == 59:
                     building_height = 32
 
-                fillBlocks(white_concrete, x, 1, z, x, building_height, z)
+                fillBlocks(wallBlock(imgAge[x][z]), x, 1, z, x, building_height, z)
             elif j >= 60 and j <= 69:  # House wall
                 building_height = 4
                 if j == 61:
@@ -282,17 +1240,45 @@ def run():
                 elif j == 69:
                     building_height = 31
 
-                if doorIncrement == 25:
+                wall = wallBlock(imgAge[x][z])
+                if isPartyWall(imgarray, x, z):
+                    fillBlocks(wall, x, 1, z, x, building_height, z)
+                elif doorIncrement == 25:
                     fillBlocks(white_stained_glass, x, 4, z, x, building_height, z)
-                    setBlock(white_concrete, x, 1, z)
+                    setBlock(wall, x, 1, z)
                     setBlock(dark_oak_door_lower, x, 2, z)
                     setBlock(dark_oak_door_upper, x, 3, z)
                     doorIncrement = 0
                 else:
-                    fillBlocks(white_concrete, x, 1, z, x, 2, z)
+                    fillBlocks(wall, x, 1, z, x, 2, z)
                     fillBlocks(white_stained_glass, x, 3, z, x, building_height, z)
                 doorIncrement += 1
-                setBlock(white_concrete, x, building_height + 1, z)
+                setBlock(wall, x, building_height + 1, z)
+            elif j >= 80 and j <= 89:  # Curtain wall
+                building_height = 4 + 3 * (int(j) - 80)
+                if (x + z) % mullionSpacing == 0:
+                    fillBlocks(gray_concrete, x, 1, z, x, building_height + 2, z)
+                else:
+                    fillBlocks(
+                        light_gray_stained_glass, x, 1, z, x, building_height, z
+                    )
+                    setBlock(light_gray_concrete, x, building_height + 1, z)
+            elif j == 90 or j == 91:  # Minecart track, station stop
+                buildTrack(imgarray, x, z, j == 91)
+            elif j == 96:  # Center line
+                setBlock(yellow_concrete, x, 1, z)
+                setBlock(air, x, 2, z)
+            elif j == 97:  # Median
+                setBlock(stone_bricks, x, 1, z)
+                setBlock(stone_brick_slab, x, 2, z)
+            elif j == 92 or j == 93:  # Power tower, power pole
+                setBlock(grass_block, x, 1, z)
+                buildPylon(x, z, j == 92)
+            elif j == 94 or j == 95:  # Power line, minor power line
+                setBlock(grass_block, x, 1, z)
+                buildCable(
+                    imgarray, x, z, powerLineCode if j == 94 else minorPowerLineCode
+                )
             elif j >= 70 and j <= 79:  # House interior
                 if j >= 70:
                     setBlock(white_concrete, x, 5, z)
@@ -319,14 +1305,74 @@ def run():
 
                 setBlock(glowstone, x, 1, z)
 
+            if metrics is not None:
+                metrics.endElement()
             z += 1
         x += 1
         ElementIncr += 1
+        if args.streamSave and x > 512 and x % 512 == streamMargin:
+            flushRegionColumn(x // 512 - 1)
+
+    cancellation.restore()
+    if cancellation.requested:
+        if not (args.yes or confirmPartialSave()):
+            if savedRegions:
+                print(f"Keeping {len(savedRegions)} regions saved by --stream-save")
+            print("Discarded the partially generated world")
+            os._exit(1)
+        print(f"Saving the first {x} of {len(imgarray)} rows")
+
+    if args.spawnPlatform:
+        buildSpawnPlatform(imgarray)
+
+    runPostProcessingPasses(setBlock, fillBlocks, imgarray)
 
+    startStage("save")
     print("Saving minecraft world...")
     saveRegion()
+    countMetric("regions", len(regions) + len(savedRegions))
+    if args.verifySave:
+        verifySave()
+    startStage("export")
+    if args.schematic is not None:
+        saveSchematic(recorder, args.schematic)
+    if args.obj is not None:
+        saveObj(recorder, args.obj)
+    if args.gltf is not None:
+        saveGltf(recorder, args.gltf)
+    if args.rcon is not None:
+        streamToServer(
+            recorder,
+            args.rcon,
+            os.environ.get("ARNIS_RCON_PASSWORD", ""),
+            args.rconRate,
+        )
+    writeMetadata(mcWorldPath, args, args.attribution, alignment)
+    writeManifest(mcWorldPath, args, dataHash, generationSeed)
+    setLevelNameSuffix(mcWorldPath, f"({args.attribution})")
+    if args.worldTime is not None:
+        setWorldTime(mcWorldPath, args.worldTime, longitude)
+    if args.worldBorder:
+        setWorldBorder(mcWorldPath, len(imgarray), len(imgarray[0]))
+    if args.pregen is not None:
+        writePregen(args.pregen, mcWorldPath, len(imgarray), len(imgarray[0]))
+    worldPaths = [mcWorldPath]
+    if args.tiles is not None:
+        worldPaths = splitIntoTiles(
+            mcWorldPath, args.tiles, len(imgarray), len(imgarray[0])
+        )
+    stages.endStage()
+    if metrics is not None:
+        metrics.write(args.metricsOut, stages.durations)
+    if args.package == "zip":
+        for worldPath in worldPaths:
+            packageWorld(worldPath, args, args.compressionLevel)
     print(
         f"Done! Finished in {(time.time() - processStartTime):.2f} "
         + f"seconds ({((time.time() - processStartTime) / 60):.2f} minutes)"
     )
+    print(f"Stages: {stages.summary()}")
+    # A cancelled run can still be completed later
+    if checkpoint is not None and not cancellation.requested:
+        checkpoint.remove()
     os._exit(0)
@@ -0,0 +1,74 @@
+import os
+import json
+import hashlib
+import subprocess
+
+# Options pointing to input files, which are hashed instead of only recorded
+inputOptions = (
+    "boundary",
+    "buildingStyle",
+    "buildingHeightsSource",
+    "overture",
+    "servers",
+    "landcover",
+    "gpx",
+    "loadIntermediate",
+)
+# May contain credentials or personal data
+privateOptions = ("proxy", "contact")
+
+
+def hashFile(path):
+    digest = hashlib.sha256()
+    with open(path, "rb") as f:
+        for block in iter(lambda: f.read(1024 * 1024), b""):
+            digest.update(block)
+    return digest.hexdigest()
+
+
+def hashData(rawdata):
+    # Key order doesn't matter, so equal data always gives the same hash
+    payload = json.dumps(rawdata, sort_keys=True, separators=(",", ":"))
+    return hashlib.sha256(payload.encode("utf-8")).hexdigest()
+
+
+def inputHashes(args):
+    hashes = {}
+    for option in inputOptions:
+        paths = getattr(args, option, None)
+        if paths is None:
+            continue
+        for path in paths if isinstance(paths, list) else [paths]:
+            if os.path.isfile(path):
+                hashes[path] = hashFile(path)
+    return hashes
+
+
+def arnisCommit():
+    try:
+        return subprocess.run(
+            ["git", "rev-parse", "HEAD"],
+            cwd=os.path.dirname(os.path.abspath(__file__)),
+            capture_output=True,
+            text=True,
+            check=True,
+        ).stdout.strip()
+    except (OSError, subprocess.CalledProcessError):
+        return None
+
+
+def writeManifest(mcWorldPath, args, dataHash, seed):
+    manifest = {
+        "generator": "Arnis",
+        "commit": arnisCommit(),
+        "seed": seed,
+        "data": dataHash,
+        "inputs": inputHashes(args),
+        "args": {
+            option: value
+            for option, value in vars(args).items()
+            if option not in privateOptions
+        },
+    }
+    with open(mcWorldPath + "/manifest.json", "w", encoding="utf-8") as f:
+        json.dump(manifest, f, indent=2, ensure_ascii=False)
@@ -0,0 +1,157 @@
+import os
+import json
+
+markerY = 2
+
+
+def toBlockCoordinates(node, minMaxDistX):
+    # The processed image is flipped on its x axis before the world is generated
+    return node[1], minMaxDistX - 1 - node[0]
+
+
+def nodeBlockCoordinates(lat, lon, georeference, minMaxDistX, minMaxDistY):
+    # Tagged nodes keep their coordinates, the ones outside the area are skipped
+    x, y = georeference.toPixel(lat, lon)
+    if not (0 <= x < minMaxDistX and 0 <= y < minMaxDistY):
+        return None
+    return toBlockCoordinates((x, y), minMaxDistX)
+
+
+def collectMarkers(data, nodes, georeference, minMaxDistX, minMaxDistY):
+    markers = []
+    for element in data["elements"]:
+        if (
+            element["type"] != "way"
+            or "tags" not in element
+            or "name" not in element["tags"]
+            or len(element["nodes"]) == 0
+        ):
+            continue
+
+        tags = element["tags"]
+        points = [toBlockCoordinates(node, minMaxDistX) for node in element["nodes"]]
+        if "highway" in tags or "railway" in tags or "waterway" in tags:
+            category = "line"
+        elif "boundary" in tags or "landuse" in tags or "natural" in tags:
+            category = "area"
+        else:
+            category = "poi"
+            points = [
+                (
+                    round(sum(point[0] for point in points) / len(points)),
+                    round(sum(point[1] for point in points) / len(points)),
+                )
+            ]
+
+        markers.append(
+            {
+                "id": "arnis_" + str(element["id"]),
+                "name": tags["name"],
+                "category": category,
+                "points": points,
+            }
+        )
+
+    # Named nodes, e.g. shops, stations and places, are always POIs
+    for elementId, tags, lat, lon in nodes:
+        point = nodeBlockCoordinates(lat, lon, georeference, minMaxDistX, minMaxDistY)
+        if point is None:
+            continue
+        markers.append(
+            {
+                "id": "arnis_node_" + str(elementId),
+                "name": tags["name"],
+                "category": "poi",
+                "points": [point],
+            }
+        )
+
+    return markers
+
+
+def dynmapMarkers(markers, world):
+    markerSet = {"label": "Arnis", "markers": {}, "lines": {}, "areas": {}}
+    for marker in markers:
+        xs = [point[0] for point in marker["points"]]
+        zs = [point[1] for point in marker["points"]]
+        if marker["category"] == "poi":
+            markerSet["markers"][marker["id"]] = {
+                "world": world,
+                "x": xs[0],
+                "y": markerY,
+                "z": zs[0],
+                "icon": "default",
+                "label": marker["name"],
+                "markup": False,
+            }
+        elif marker["category"] == "line":
+            markerSet["lines"][marker["id"]] = {
+                "world": world,
+                "x": xs,
+                "y": [markerY] * len(xs),
+                "z": zs,
+                "label": marker["name"],
+                "markup": False,
+            }
+        else:
+            markerSet["areas"][marker["id"]] = {
+                "world": world,
+                "x": xs,
+                "z": zs,
+                "ytop": markerY,
+                "ybottom": markerY,
+                "label": marker["name"],
+                "markup": False,
+            }
+
+    return {"sets": {"arnis": markerSet}}
+
+
+def bluemapMarkers(markers):
+    markerSet = {"label": "Arnis", "markers": {}}
+    for marker in markers:
+        if marker["category"] == "poi":
+            markerSet["markers"][marker["id"]] = {
+                "type": "poi",
+                "label": marker["name"],
+                "position": {
+                    "x": marker["points"][0][0],
+                    "y": markerY,
+                    "z": marker["points"][0][1],
+                },
+            }
+        elif marker["category"] == "line":
+            markerSet["markers"][marker["id"]] = {
+                "type": "line",
+                "label": marker["name"],
+                "line": [
+                    {"x": point[0], "y": markerY, "z": point[1]}
+                    for point in marker["points"]
+                ],
+            }
+        else:
+            markerSet["markers"][marker["id"]] = {
+                "type": "shape",
+                "label": marker["name"],
+                "shape": [{"x": point[0], "z": point[1]} for point in marker["points"]],
+                "shape-y": markerY,
+            }
+
+    return {"arnis": markerSet}
+
+
+def writeMarkers(
+    mode, data, nodes, georeference, minMaxDistX, minMaxDistY, mcWorldPath
+):
+    markers = collectMarkers(data, nodes, georeference, minMaxDistX, minMaxDistY)
+    # JSON is a subset of YAML, so Dynmap can read this file as it is
+    if mode == "dynmap":
+        fileName = mcWorldPath + "/arnis-markers.yml"
+        content = dynmapMarkers(markers, os.path.basename(mcWorldPath))
+    else:
+        fileName = mcWorldPath + "/arnis-markers.json"
+        content = bluemapMarkers(markers)
+
+    with open(fileName, "w", encoding="utf-8") as f:
+        json.dump(content, f, indent=2, ensure_ascii=False)
+    print(f"Exported {len(markers)} markers to {fileName}")
@@ -0,0 +1,24 @@
+from math import ceil
+
+# Generated blocks reach up to y=35, so every chunk holds three sections
+# of 4096 references to shared block objects, plus some overhead
+bytesPerChunk = 3 * 4096 * 8 + 4096
+regionSize = 512
+
+
+def chunkCount(sizeX, sizeZ):
+    return ceil(sizeX / 16) * ceil(sizeZ / 16)
+
+
+def estimateMemory(sizeX, sizeZ, streamSave=False, recordedHeight=0):
+    # Rough estimate in bytes. The recorded blocks of --schematic, --obj, ...
+    # are stored as uint16 for the whole area, also when streaming.
+    recorded = recordedHeight * sizeX * sizeZ * 2
+    # Streaming only keeps two columns of regions
+    if streamSave:
+        sizeX = min(sizeX, 2 * regionSize)
+    return chunkCount(sizeX, sizeZ) * bytesPerChunk + recorded
+
+
+def formatGigabytes(size):
+    return f"{size / 1024**3:.1f} GB"
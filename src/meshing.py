@@ -0,0 +1,103 @@
+import zlib
+import numpy as np
+
+# Approximate colors (RGB) of the most common generated blocks
+blockColors = {
+    "minecraft:dirt": (134, 96, 67),
+    "minecraft:grass_block": (95, 159, 53),
+    "minecraft:sand": (219, 207, 163),
+    "minecraft:water": (63, 118, 228),
+    "minecraft:stone": (125, 125, 125),
+    "minecraft:black_concrete": (8, 10, 15),
+    "minecraft:gray_concrete": (55, 58, 62),
+    "minecraft:light_gray_concrete": (125, 125, 115),
+    "minecraft:white_concrete": (207, 213, 214),
+    "minecraft:white_stained_glass": (230, 230, 230),
+    "minecraft:light_gray_stained_glass": (153, 153, 153),
+    "minecraft:cobblestone": (122, 122, 122),
+    "minecraft:oak_log": (109, 85, 50),
+    "minecraft:oak_leaves": (60, 110, 40),
+    "minecraft:oak_planks": (162, 130, 78),
+    "minecraft:farmland": (81, 44, 15),
+    "minecraft:bricks": (150, 97, 83),
+    "minecraft:stone_bricks": (122, 121, 122),
+    "minecraft:quartz_block": (236, 230, 223),
+    "minecraft:iron_block": (220, 220, 220),
+}
+
+
+def materialColor(state):
+    name = state.split("[")[0]
+    if name in blockColors:
+        return blockColors[name]
+    # Stable made up color for everything else
+    checksum = zlib.crc32(name.encode("utf-8"))
+    return (checksum & 0xFF, (checksum >> 8) & 0xFF, (checksum >> 16) & 0xFF)
+
+
+def greedyQuads(layer):
+    # Merges equal runs of consecutive rows, returns (row0, row1, col0, col1, id)
+    quads = []
+    active = {}
+    for row in range(layer.shape[0] + 1):
+        runs = set()
+        if row < layer.shape[0]:
+            values = layer[row]
+            changes = np.nonzero(np.diff(values))[0] + 1
+            starts = np.concatenate(([0], changes))
+            ends = np.concatenate((changes, [len(values)]))
+            runs = {
+                (int(start), int(end), int(values[start]))
+                for start, end in zip(starts, ends)
+                if values[start] != 0
+            }
+        for run in [run for run in active if run not in runs]:
+            quads.append((active.pop(run), row, run[0], run[1], run[2]))
+        for run in runs:
+            if run not in active:
+                active[run] = row
+    return quads
+
+
+def meshQuads(blocks):
+    # Returns the quads next to air as {palette index: [(corners, normal)]}
+    grid = blocks.transpose(2, 0, 1)  # Recorded as y, z, x
+    solid = grid != 0
+    padded = np.pad(solid, 1)
+    quads = {}
+    for axis in range(3):
+        # Cyclic axes, so the corners below wind counterclockwise around +axis
+        u, v = (axis + 1) % 3, (axis + 2) % 3
+        for sign in (1, -1):
+            shift = [1, 1, 1]
+            shift[axis] += sign
+            neighbour = padded[
+                shift[0] : shift[0] + grid.shape[0],
+                shift[1] : shift[1] + grid.shape[1],
+                shift[2] : shift[2] + grid.shape[2],
+            ]
+            faces = np.moveaxis(
+                np.where(solid & ~neighbour, grid, 0), (axis, u, v), (0, 1, 2)
+            )
+            normal = [0, 0, 0]
+            normal[axis] = sign
+            for index in range(faces.shape[0]):
+                if not faces[index].any():
+                    continue
+                for row0, row1, col0, col1, material in greedyQuads(faces[index]):
+                    corners = []
+                    for row, col in (
+                        (row0, col0),
+                        (row1, col0),
+                        (row1, col1),
+                        (row0, col1),
+                    ):
+                        corner = [0, 0, 0]
+                        corner[axis] = index + (1 if sign > 0 else 0)
+                        corner[u] = row
+                        corner[v] = col
+                        corners.append(corner)
+                    if sign < 0:
+                        corners.reverse()
+                    quads.setdefault(material, []).append((corners, normal))
+    return quads
@@ -0,0 +1,19 @@
+import json
+
+defaultAttribution = "Map data © OpenStreetMap contributors, ODbL"
+
+
+def writeMetadata(mcWorldPath, args, attribution, alignment=None):
+    metadata = {
+        "generator": "Arnis",
+        "city": args.city,
+        "state": args.state,
+        "country": args.country,
+        "attribution": attribution,
+        "license": "ODbL-1.0",
+        "licenseUrl": "https://opendatacommons.org/licenses/odbl/",
+    }
+    if alignment is not None:
+        metadata["alignment"] = alignment
+    with open(mcWorldPath + "/metadata.json", "w", encoding="utf-8") as f:
+        json.dump(metadata, f, indent=2, ensure_ascii=False)
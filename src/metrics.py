@@ -0,0 +1,85 @@
+import sys
+import json
+import time
+
+try:
+    import resource
+except ImportError:  # Not available on Windows
+    resource = None
+
+# First and last ID of every kind of element, see the ID table in the README
+elementKinds = [
+    (0, 0, "ground"),
+    (10, 14, "roads and railways"),
+    (15, 19, "paths"),
+    (20, 24, "amenities"),
+    (25, 28, "nature"),
+    (29, 29, "street lamps"),
+    (30, 39, "landuse"),
+    (40, 40, "ocean"),
+    (41, 49, "tinted ground"),
+    (50, 89, "buildings"),
+    (90, 91, "minecarts"),
+    (92, 95, "power lines"),
+    (96, 97, "roads and railways"),
+]
+
+
+def elementKind(code):
+    for first, last, kind in elementKinds:
+        if first <= code <= last:
+            return kind
+    return "other"
+
+
+def peakMemory():
+    # Peak resident set size in bytes, ru_maxrss is in bytes on macOS and in
+    # kilobytes everywhere else
+    if resource is None:
+        return None
+    maxrss = resource.getrusage(resource.RUSAGE_SELF).ru_maxrss
+    return maxrss if sys.platform == "darwin" else maxrss * 1024
+
+
+class MetricsRecorder:
+    def __init__(self):
+        self.counters = {}
+        self.kinds = {}
+        self.kind = None
+        self.start = 0
+
+    def count(self, name, amount=1):
+        self.counters[name] = self.counters.get(name, 0) + amount
+
+    def startElement(self, code):
+        self.kind = self.kinds.setdefault(
+            elementKind(int(code)), {"seconds": 0, "pixels": 0, "blocks": 0}
+        )
+        self.start = time.perf_counter()
+
+    def endElement(self):
+        self.kind["seconds"] += time.perf_counter() - self.start
+        self.kind["pixels"] += 1
+        self.kind = None
+
+    def countBlock(self, amount=1):
+        # Blocks set outside of an element, e.g. by plugins, aren't counted
+        if self.kind is not None:
+            self.kind["blocks"] += amount
+
+    def report(self, stages=None):
+        # Slowest kinds first
+        kinds = sorted(
+            self.kinds.items(), key=lambda item: item[1]["seconds"], reverse=True
+        )
+        return {
+            "stages": stages or {},
+            "counters": self.counters,
+            "elements": dict(kinds),
+            "peakMemory": peakMemory(),
+        }
+
+    def write(self, path, stages=None):
+        with open(path, "w", encoding="utf-8") as f:
+            json.dump(self.report(stages), f, indent=2)
+        print(f"Wrote metrics to {path}")
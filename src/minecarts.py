@@ -0,0 +1,60 @@
+from .bresenham import bresenham
+
+trackCode = 90
+stationCode = 91
+stationDistance = 3
+
+
+def connectedLine(points):
+    # Minecarts can't follow diagonal steps, so every step gets a corner
+    line = []
+    for point in points:
+        if len(line) > 0 and point[0] != line[-1][0] and point[1] != line[-1][1]:
+            line.append((point[0], line[-1][1]))
+        line.append(point)
+    return line
+
+
+def drawTrack(img, start, end):
+    for x, y in connectedLine(bresenham(start[0], start[1], end[0], end[1])):
+        if x < img.shape[1] and y < img.shape[0]:
+            img[y][x] = trackCode
+
+
+def isPlatform(tags):
+    return tags.get("railway") == "platform" or tags.get("public_transport") in (
+        "platform",
+        "station",
+    )
+
+
+def markStations(img, data):
+    # Track next to a platform becomes a stop
+    for element in data["elements"]:
+        if element["type"] != "way" or not isPlatform(element.get("tags", {})):
+            continue
+        previous = None
+        for node in element["nodes"]:
+            if previous is not None:
+                for x, y in bresenham(node[0], node[1], previous[0], previous[1]):
+                    nearby = img[
+                        max(y - stationDistance, 0) : y + stationDistance + 1,
+                        max(x - stationDistance, 0) : x + stationDistance + 1,
+                    ]
+                    nearby[nearby == trackCode] = stationCode
+            previous = node
+    return img
+
+
+def railShape(north, south, east, west):
+    if (east or west) and not (north or south):
+        return "east_west"
+    if south and east:
+        return "south_east"
+    if south and west:
+        return "south_west"
+    if north and east:
+        return "north_east"
+    if north and west:
+        return "north_west"
+    return "north_south"
@@ -0,0 +1,35 @@
+import os
+
+from .meshing import materialColor, meshQuads
+
+
+def materialName(state):
+    for character in ":[]=,":
+        state = state.replace(character, "_")
+    return state
+
+
+def saveObj(recorder, path):
+    quads = meshQuads(recorder.blocks)
+    states = {index: state for state, index in recorder.palette.items()}
+
+    mtlPath = os.path.splitext(path)[0] + ".mtl"
+    with open(mtlPath, "w", encoding="utf-8") as f:
+        for material in sorted(quads):
+            color = materialColor(states[material])
+            f.write(f"newmtl {materialName(states[material])}\n")
+            f.write("Kd " + " ".join(f"{value / 255:.3f}" for value in color) + "\n")
+
+    faceCount = 0
+    with open(path, "w", encoding="utf-8") as f:
+        f.write(f"mtllib {os.path.basename(mtlPath)}\n")
+        for material in sorted(quads):
+            f.write(f"usemtl {materialName(states[material])}\n")
+            for corners, _ in quads[material]:
+                for corner in corners:
+                    f.write(f"v {corner[0]} {corner[1]} {corner[2]}\n")
+                # OBJ indices are 1-based and count all previous vertices
+                first = faceCount * 4 + 1
+                f.write(f"f {first} {first + 1} {first + 2} {first + 3}\n")
+                faceCount += 1
+    print(f"Saved 3D model {path} ({faceCount} faces)")
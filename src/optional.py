@@ -0,0 +1,11 @@
+import os
+import importlib
+
+
+def importOptional(module, option, package):
+    # Optional data sources pull in large packages which most users don't need
+    try:
+        return importlib.import_module(module, __package__)
+    except ImportError:
+        print(f"Error! {option} requires the {package} package: pip install {package}")
+        os._exit(1)
@@ -0,0 +1,177 @@
+import os
+import struct
+import pyarrow.parquet as pq
+
+
+def readWkb(wkb, offset=0):
+    byteOrder = "<" if wkb[offset] == 1 else ">"
+    geometryType = struct.unpack_from(byteOrder + "I", wkb, offset + 1)[0]
+    offset += 5
+    # Ignore Z/M dimensions flagged the ISO way, only 2D geometries are supported
+    geometryType %= 1000
+
+    def readPoints(offset):
+        count = struct.unpack_from(byteOrder + "I", wkb, offset)[0]
+        offset += 4
+        points = []
+        for _ in range(count):
+            lon, lat = struct.unpack_from(byteOrder + "dd", wkb, offset)
+            points.append((lat, lon))
+            offset += 16
+        return points, offset
+
+    if geometryType == 1:
+        lon, lat = struct.unpack_from(byteOrder + "dd", wkb, offset)
+        return ("Point", [(lat, lon)]), offset + 16
+    if geometryType == 2:
+        points, offset = readPoints(offset)
+        return ("LineString", points), offset
+    if geometryType == 3:
+        ringCount = struct.unpack_from(byteOrder + "I", wkb, offset)[0]
+        offset += 4
+        rings = []
+        for _ in range(ringCount):
+            ring, offset = readPoints(offset)
+            rings.append(ring)
+        return ("Polygon", rings), offset
+    if geometryType == 6:
+        polygonCount = struct.unpack_from(byteOrder + "I", wkb, offset)[0]
+        offset += 4
+        polygons = []
+        for _ in range(polygonCount):
+            polygon, offset = readWkb(wkb, offset)
+            polygons.append(polygon[1])
+        return ("MultiPolygon", polygons), offset
+
+    raise ValueError(f"Unsupported WKB geometry type {geometryType}")
+
+
+# Overture place categories with a matching OSM tag, all others become amenities
+placeCategories = {
+    "train_station": ("railway", "station"),
+    "hotel": ("tourism", "hotel"),
+    "museum": ("tourism", "museum"),
+    "landmark_and_historical_building": ("historic", "yes"),
+    "park": ("leisure", "park"),
+}
+
+
+def featureTags(theme, row):
+    tags = {}
+    names = row.get("names")
+    if names is not None and names.get("primary") is not None:
+        tags["name"] = names["primary"]
+
+    if theme == "buildings":
+        tags["building"] = row.get("class") or "yes"
+        if row.get("height") is not None:
+            tags["height"] = str(round(row["height"]))
+        if row.get("num_floors") is not None:
+            tags["building:levels"] = str(row["num_floors"])
+    elif theme == "transportation":
+        if row.get("subtype") == "rail":
+            tags["railway"] = row.get("class") or "rail"
+        elif row.get("subtype") == "water":
+            return None
+        else:
+            roadClass = row.get("class")
+            tags["highway"] = "road" if roadClass in (None, "unknown") else roadClass
+    elif theme == "places":
+        categories = row.get("categories")
+        if "name" not in tags or categories is None:
+            return None
+        category = categories.get("primary")
+        if category is None:
+            return None
+        key, value = placeCategories.get(category, ("amenity", category))
+        tags[key] = value
+    return tags
+
+
+def detectTheme(columns):
+    if "num_floors" in columns or "height" in columns:
+        return "buildings"
+    if "connectors" in columns or "connector_ids" in columns:
+        return "transportation"
+    if "categories" in columns:
+        return "places"
+    return None
+
+
+class ElementBuilder:
+    def __init__(self):
+        self.nodeIds = {}
+        self.elements = []
+        self.nextNodeId = 1
+        self.nextWayId = 1
+
+    def node(self, point):
+        # Overture has no shared nodes, so they are merged by their coordinates
+        point = (round(point[0], 7), round(point[1], 7))
+        if point not in self.nodeIds:
+            self.nodeIds[point] = self.nextNodeId
+            self.elements.append(
+                {
+                    "type": "node",
+                    "id": self.nextNodeId,
+                    "lat": point[0],
+                    "lon": point[1],
+                }
+            )
+            self.nextNodeId += 1
+        return self.nodeIds[point]
+
+    def place(self, point, tags):
+        # Places are tagged nodes of their own, never merged into ways
+        self.elements.append(
+            {
+                "type": "node",
+                "id": self.nextNodeId,
+                "lat": round(point[0], 7),
+                "lon": round(point[1], 7),
+                "tags": tags,
+            }
+        )
+        self.nextNodeId += 1
+
+    def way(self, points, tags):
+        self.elements.append(
+            {
+                "type": "way",
+                "id": self.nextWayId,
+                "nodes": [self.node(point) for point in points],
+                "tags": tags,
+            }
+        )
+        self.nextWayId += 1
+
+
+def getOvertureData(paths):
+    print("Reading Overture data...")
+    builder = ElementBuilder()
+    for path in paths:
+        table = pq.read_table(path)
+        theme = detectTheme(table.column_names)
+        if theme is None:
+            print(f"Warning! Skipping unsupported Overture theme in {path}")
+            continue
+
+        for row in table.to_pylist():
+            tags = featureTags(theme, row)
+            if tags is None or row.get("geometry") is None:
+                continue
+            (geometryType, coordinates), _ = readWkb(row["geometry"])
+            if geometryType == "Point":
+                builder.place(coordinates[0], tags)
+            elif geometryType == "LineString":
+                builder.way(coordinates, tags)
+            elif geometryType == "Polygon":
+                builder.way(coordinates[0], tags)
+            elif geometryType == "MultiPolygon":
+                for polygon in coordinates:
+                    builder.way(polygon[0], dict(tags))
+
+    if builder.nextWayId == 1:
+        print("Error! No data available")
+        os._exit(1)
+    return {"elements": builder.elements}
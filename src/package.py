@@ -0,0 +1,39 @@
+import os
+import zipfile
+
+
+def describeGeneration(args):
+    area = [part for part in (args.city, args.state, args.country) if part]
+    lines = [
+        "Generated with Arnis (https://github.com/louis-e/arnis)",
+        "",
+        f"Area: {', '.join(area) or 'unknown'}",
+        f"Attribution: {args.attribution}",
+        "",
+        "See metadata.json and manifest.json for the license and all options.",
+    ]
+    return "\n".join(lines) + "\n"
+
+
+def packageWorld(mcWorldPath, args, compressionLevel=None):
+    # The archive contains the world folder itself, like worlds downloaded
+    # from map sharing sites, so it can be extracted into the saves folder
+    worldName = os.path.basename(mcWorldPath)
+    archivePath = mcWorldPath + ".zip"
+    # Region files are compressed already, higher levels mostly cost time
+    with zipfile.ZipFile(
+        archivePath, "w", zipfile.ZIP_DEFLATED, compresslevel=compressionLevel
+    ) as archive:
+        for directory, _, files in os.walk(mcWorldPath):
+            for name in sorted(files):
+                # Left behind by a running game, unreadable on Windows
+                if name == "session.lock":
+                    continue
+                path = os.path.join(directory, name)
+                archive.write(
+                    path,
+                    os.path.join(worldName, os.path.relpath(path, mcWorldPath)),
+                )
+        archive.writestr(worldName + "/README.txt", describeGeneration(args))
+    print(f"Packaged the world as {archivePath}")
+    return archivePath
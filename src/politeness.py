@@ -0,0 +1,24 @@
+# Shared settings for all requests to public services (Overpass, Nominatim,
+# Geofabrik and tile servers), set once from the command line
+userAgent = "Arnis (https://github.com/louis-e/arnis)"
+polite = False
+
+# Used in polite mode, regardless of the configured number of workers
+politeRequestsPerSecond = 1
+politeDelay = 10
+
+
+def configure(politeMode, contact=None):
+    global userAgent, polite
+    polite = politeMode
+    if contact is not None:
+        # Lets server operators reach out instead of blocking the address
+        userAgent = f"Arnis (https://github.com/louis-e/arnis; {contact})"
+
+
+def requestHeaders():
+    return {"User-Agent": userAgent}
+
+
+def minimumDelay():
+    return politeDelay if polite else 0
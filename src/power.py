@@ -0,0 +1,59 @@
+from .bresenham import bresenham
+
+powerTowerCode = 92
+powerPoleCode = 93
+powerLineCode = 94
+minorPowerLineCode = 95
+# Towers carry power=line, poles the lower power=minor_line
+pylonCodes = {"tower": powerTowerCode, "pole": powerPoleCode}
+lineCodes = {
+    "line": (powerLineCode, powerTowerCode),
+    "minor_line": (minorPowerLineCode, powerPoleCode),
+}
+# Height of the cables, the pylons are one block higher
+cableHeights = {powerLineCode: 14, minorPowerLineCode: 6}
+# Cables hang over open ground only, roads and buildings keep their pixels
+cableGround = (0, 30, 31, 32, 33, 34, 35, 36, 39)
+pylonGround = cableGround + (powerLineCode, minorPowerLineCode)
+
+
+def cableSag(distance, cableCode):
+    # How far a cable hangs below its height at a distance from the nearest
+    # pylon, deepest in the middle of a span like a catenary
+    maxSag = 3 if cableCode == powerLineCode else 1
+    return min(distance // 6, maxSag)
+
+
+def setPylon(img, x, y, code):
+    if 0 <= y < img.shape[0] and 0 <= x < img.shape[1] and img[y][x] in pylonGround:
+        img[y][x] = code
+
+
+def drawPowerLines(img, data, points, georeference):
+    pylons = []
+    for element in data["elements"]:
+        if element["type"] != "way":
+            continue
+        power = element.get("tags", {}).get("power")
+        if power not in lineCodes:
+            continue
+        cableCode, pylonCode = lineCodes[power]
+        previous = None
+        for node in element["nodes"]:
+            if previous is not None:
+                for x, y in bresenham(node[0], node[1], previous[0], previous[1]):
+                    if img[y][x] in cableGround:
+                        img[y][x] = cableCode
+            # Every vertex of a power line is a pylon
+            pylons.append((node[0], node[1], pylonCode))
+            previous = node
+
+    # Pylons which are mapped without a line, e.g. where it leaves the area
+    for tags, lat, lon in points:
+        if tags.get("power") in pylonCodes:
+            x, y = georeference.toPixel(lat, lon)
+            pylons.append((x, y, pylonCodes[tags["power"]]))
+
+    for x, y, code in pylons:
+        setPylon(img, x, y, code)
+    return img
@@ -0,0 +1,63 @@
+import os
+from math import ceil
+
+
+def createDatapack(mcWorldPath):
+    # Shared by all generated functions, returns the function folder
+    datapackPath = mcWorldPath + "/datapacks/arnis"
+    functionPath = datapackPath + "/data/arnis/functions"
+    os.makedirs(functionPath, exist_ok=True)
+
+    with open(datapackPath + "/pack.mcmeta", "w", encoding="utf-8") as f:
+        f.write('{"pack": {"pack_format": 10, "description": "Arnis"}}\n')
+    return functionPath
+
+
+def writeForceloadDatapack(mcWorldPath, sizeX, sizeZ):
+    functionPath = createDatapack(mcWorldPath)
+
+    chunksX = ceil(sizeX / 16)
+    chunksZ = ceil(sizeZ / 16)
+    commands = []
+    # Vanilla refuses to forceload more than 256 chunks in a single command
+    for startX in range(0, chunksX, 16):
+        for startZ in range(0, chunksZ, 16):
+            endX = min(startX + 16, chunksX) - 1
+            endZ = min(startZ + 16, chunksZ) - 1
+            commands.append(
+                f"forceload add {startX * 16} {startZ * 16} "
+                + f"{endX * 16 + 15} {endZ * 16 + 15}"
+            )
+
+    with open(functionPath + "/forceload.mcfunction", "w", encoding="utf-8") as f:
+        f.write("\n".join(commands) + "\n")
+    with open(functionPath + "/unforceload.mcfunction", "w", encoding="utf-8") as f:
+        f.write("forceload remove all\n")
+
+    print(
+        f"Wrote {len(commands)} forceload tickets, "
+        + "run /function arnis:forceload once the world is loaded"
+    )
+
+
+def writeChunkyConfig(mcWorldPath, sizeX, sizeZ):
+    radiusX = ceil(sizeX / 2)
+    radiusZ = ceil(sizeZ / 2)
+    commands = [
+        "chunky world " + os.path.basename(mcWorldPath),
+        "chunky shape rectangle",
+        f"chunky center {radiusX} {radiusZ}",
+        f"chunky radius {radiusX} {radiusZ}",
+        "chunky start",
+    ]
+
+    with open(mcWorldPath + "/arnis-chunky.txt", "w", encoding="utf-8") as f:
+        f.write("\n".join(commands) + "\n")
+    print(f"Wrote Chunky pregeneration commands to {mcWorldPath}/arnis-chunky.txt")
+
+
+def writePregen(mode, mcWorldPath, sizeX, sizeZ):
+    if mode == "forceload":
+        writeForceloadDatapack(mcWorldPath, sizeX, sizeZ)
+    elif mode == "chunky":
+        writeChunkyConfig(mcWorldPath, sizeX, sizeZ)
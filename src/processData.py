@@ -3,7 +3,40 @@ from cv2 import imwrite
 import numpy as np
 
 from .bresenham import bresenham
-from .floodFill import floodFill
+from .fillBudget import FillBudget
+from .buildingHeight import getBuildingHeight, isCurtainWall, loadBuildingStyle
+from .areaDetection import isArea
+from .deduplicate import deduplicate
+from .relations import assembleMultipolygons
+from .boundary import (
+    addBoundary,
+    getBoundaryPolygon,
+    loadBoundary,
+    maskOutsideBoundary,
+    polygonMask,
+)
+from .coastline import getIslandPolygons, getOceanPolygons
+from .georeference import Georeference
+from .landcover import sampleLandcover
+from .imagery import sampleImagery
+from .gpx import drawGpx
+from .promenade import addPromenade
+from .power import drawPowerLines
+from .rocks import bareRockCode, placeRocks
+from .roadMarkings import paintRoadMarkings
+from .roadWidth import hasMedian, medianCode, roadBlockRange
+from .roundabouts import drawRoundabout, isRoundabout
+from .sidewalks import drawSidewalks, paintCrossings
+from .streetLamps import placeStreetLamps
+from .viewpoints import placeViewpoints
+from .ferries import drawFerries
+from .minecarts import drawTrack, markStations
+from .buildingFootprints import loadFootprints, mergeFootprints
+from .buildingAge import getAgeClass
+from .markers import writeMarkers
+from .teleports import writeTeleports
+from .rasters import exportRasters
+from .progress import Progress
 
 
 def processData(data, args):
@@ -11,10 +44,55 @@ def processData(data, args):
     resDownScaler = 100
     processingStartTime = time()
 
+    removedDuplicates = deduplicate(data)
+    if removedDuplicates > 0:
+        print(f"Removed {removedDuplicates} duplicate elements")
+
+    addedRings, skippedRelations = assembleMultipolygons(data)
+    if addedRings > 0:
+        print(f"Assembled {addedRings} areas from multipolygon relations")
+    if skippedRelations:
+        print(
+            "Skipped unsupported relations: "
+            + ", ".join(
+                f"{count} {relationType}"
+                for relationType, count in skippedRelations.most_common()
+            )
+        )
+
+    if args.buildingHeightsSource is not None:
+        updated, added = mergeFootprints(
+            data, loadFootprints(args.buildingHeightsSource)
+        )
+        print(f"Building footprints: {updated} heights updated, {added} added")
+
+    if args.boundary is not None:
+        addBoundary(data, loadBoundary(args.boundary))
+
+    # Tagged nodes keep their original coordinates and are placed by georeference
+    points = [
+        (element["tags"], element["lat"], element["lon"])
+        for element in data["elements"]
+        if element["type"] == "node" and "tags" in element
+    ]
+    # Markers and teleports also need the ids of the named ones
+    namedNodes = [
+        (element["id"], element["tags"], element["lat"], element["lon"])
+        for element in data["elements"]
+        if element["type"] == "node" and "name" in element.get("tags", {})
+    ]
+
     greatestElementX = 0
     greatestElementY = 0
+    # Digits without the sign, so areas west of Greenwich or south of the
+    # equator are padded and scaled like all others
+    greatestDigitsX = 0
+    greatestDigitsY = 0
+    scale = [None, None]
     for element in data["elements"]:
         if element["type"] == "node":
+            lat = element["lat"]
+            lon = element["lon"]
             element["lat"] = int(str(element["lat"]).replace(".", ""))
             element["lon"] = int(str(element["lon"]).replace(".", ""))
 
@@ -22,20 +100,19 @@ def processData(data, args):
                 greatestElementX = element["lat"]
             if element["lon"] > greatestElementY:
                 greatestElementY = element["lon"]
+            if len(str(abs(element["lat"]))) > greatestDigitsX and lat != 0:
+                greatestDigitsX = len(str(abs(element["lat"])))
+                scale[0] = abs(element["lat"] / lat)
+            if len(str(abs(element["lon"]))) > greatestDigitsY and lon != 0:
+                greatestDigitsY = len(str(abs(element["lon"])))
+                scale[1] = abs(element["lon"] / lon)
 
     for element in data["elements"]:
         if element["type"] == "node":
-            if len(str(element["lat"])) != len(str(greatestElementX)):
-                for i in range(
-                    0, len(str(greatestElementX)) - len(str(element["lat"]))
-                ):
-                    element["lat"] *= 10
-
-            if len(str(element["lon"])) != len(str(greatestElementY)):
-                for i in range(
-                    0, len(str(greatestElementY)) - len(str(element["lon"]))
-                ):
-                    element["lon"] *= 10
+            for i in range(0, greatestDigitsX - len(str(abs(element["lat"])))):
+                element["lat"] *= 10
+            for i in range(0, greatestDigitsY - len(str(abs(element["lon"])))):
+                element["lon"] *= 10
 
     lowestElementX = greatestElementX
     lowestElementY = greatestElementY
@@ -100,6 +177,12 @@ def processData(data, args):
     maxBuilding = (maxBuilding[0] + 50, maxBuilding[1] + 50)
     minMaxDistX = maxBuilding[0] - minBuilding[0]
     minMaxDistY = maxBuilding[1] - minBuilding[1]
+    # Areas without nodes have nothing to place
+    georeference = None
+    if None not in scale:
+        georeference = Georeference(
+            tuple(scale), (lowestElementX, lowestElementY), minBuilding, resDownScaler
+        )
 
     for i, element in enumerate(data["elements"]):
         if element["type"] == "way":
@@ -129,6 +212,21 @@ def processData(data, args):
                 if element["nodes"][j][1] >= minMaxDistY:
                     element["nodes"][j][1] = minMaxDistY - 1
 
+    if args.markers is not None:
+        writeMarkers(
+            args.markers,
+            data,
+            namedNodes,
+            georeference,
+            minMaxDistX,
+            minMaxDistY,
+            args.path,
+        )
+    if args.teleports:
+        writeTeleports(
+            data, namedNodes, georeference, minMaxDistX, minMaxDistY, args.path
+        )
+
     if args.debug:
         print(f"minMaxDistX: {minMaxDistX}")
         print(f"minMaxDistY: {minMaxDistY}")
@@ -158,52 +256,41 @@ def processData(data, args):
 
     img.fill(0)
     imgLanduse = img.copy()
+    imgAge = img.copy()
+
+    buildingStyle = loadBuildingStyle(args.buildingStyle)
+
+    fillBudget = FillBudget(
+        sum(
+            1
+            for element in data["elements"]
+            if element["type"] == "way" and isArea(element)
+        ),
+        args.fillBudget,
+        args.fillAlgorithm,
+    )
 
     print("Processing data...")
 
     ElementIncr = 0
-    ElementsLen = len(data["elements"])
-    lastProgressPercentage = 0
+    progress = Progress("Element", len(data["elements"]))
     for element in reversed(data["elements"]):
-        progressPercentage = round(100 * (ElementIncr + 1) / ElementsLen)
-        if (
-            progressPercentage % 10 == 0
-            and progressPercentage != lastProgressPercentage
-        ):
-            print(f"Element {ElementIncr + 1}/{ElementsLen} ({progressPercentage}%)")
-            lastProgressPercentage = progressPercentage
+        progress.update(ElementIncr + 1)
 
         if element["type"] == "way" and "tags" in element:
             if "building" in element["tags"]:
                 previousElement = (0, 0)
                 cornerAddup = (0, 0, 0)
                 currentBuilding = np.array([[0, 0]])
+                buildingHeight = getBuildingHeight(
+                    element, args.rescaleHeights, buildingStyle
+                )
+                wallType = "6"
+                if isCurtainWall(element, buildingHeight, buildingStyle):
+                    wallType = "8"
+                ageClass = getAgeClass(element["tags"])
                 for coordinate in element["nodes"]:
-                    buildingHeight = 1
-
                     if previousElement != (0, 0):
-                        if "height" in element["tags"]:
-                            if len(element["tags"]["height"]) >= 3:
-                                buildingHeight = 9
-                            elif len(element["tags"]["height"]) == 1:
-                                buildingHeight = 2
-                            elif element["tags"]["height"][:1] == "1":
-                                buildingHeight = 3
-                            elif element["tags"]["height"][:1] == "2":
-                                buildingHeight = 6
-                            else:
-                                buildingHeight = 9
-
-                        if (
-                            "building:levels" in element["tags"]
-                            and element["tags"]["building:levels"].isnumeric()
-                            and int(float(element["tags"]["building:levels"])) <= 8
-                            and int(float(element["tags"]["building:levels"])) >= 1
-                        ):
-                            buildingHeight = str(
-                                int(float(element["tags"]["building:levels"])) - 1
-                            )
-
                         for i in bresenham(
                             coordinate[0],
                             coordinate[1],
@@ -211,10 +298,11 @@ def processData(data, args):
                             previousElement[1],
                         ):
                             if not (
-                                str(img[i[1]][i[0]][0])[:1] == "6"
-                                and img[i[1]][i[0]][0] > int("6" + str(buildingHeight))
+                                str(img[i[1]][i[0]][0])[:1] in ("6", "8")
+                                and img[i[1]][i[0]][0] % 10 > buildingHeight
                             ):
-                                img[i[1]][i[0]] = int("6" + str(buildingHeight))
+                                img[i[1]][i[0]] = int(wallType + str(buildingHeight))
+                                imgAge[i[1]][i[0]] = ageClass
 
                         currentBuilding = np.append(
                             currentBuilding, [[coordinate[0], coordinate[1]]], axis=0
@@ -228,6 +316,7 @@ def processData(data, args):
                             img[coordinate[1]][coordinate[0]] = int(
                                 "5" + str(buildingHeight)
                             )
+                            imgAge[coordinate[1]][coordinate[0]] = ageClass
 
                         if not (
                             str(img[previousElement[1]][previousElement[0]][0])[:1]
@@ -238,6 +327,7 @@ def processData(data, args):
                             img[previousElement[1]][previousElement[0]] = int(
                                 "5" + str(buildingHeight)
                             )
+                            imgAge[previousElement[1]][previousElement[0]] = ageClass
 
                         cornerAddup = (
                             cornerAddup[0] + coordinate[0],
@@ -246,8 +336,8 @@ def processData(data, args):
                         )
                     previousElement = (coordinate[0], coordinate[1])
 
-                if cornerAddup != (0, 0, 0):
-                    img = floodFill(
+                if cornerAddup != (0, 0, 0) and isArea(element):
+                    img = fillBudget.fill(
                         img,
                         round(cornerAddup[1] / cornerAddup[2]),
                         round(cornerAddup[0] / cornerAddup[2]),
@@ -258,6 +348,11 @@ def processData(data, args):
                         elementType="building",
                     )
 
+            elif "highway" in element["tags"] and isRoundabout(element):
+                img = drawRoundabout(
+                    img, element["nodes"], roadBlockRange(element["tags"])
+                )
+
             elif "highway" in element["tags"]:
                 previousElement = (0, 0)
                 for coordinate in element["nodes"]:
@@ -269,8 +364,9 @@ def processData(data, args):
                         and element["tags"]["highway"] != "steps"
                         and element["tags"]["highway"] != "bridge"
                     ):
-                        blockRange = 2
+                        blockRange = roadBlockRange(element["tags"])
                         highwayType = 10
+                        median = hasMedian(element["tags"])
 
                         if (
                             element["tags"]["highway"] == "path"
@@ -278,17 +374,11 @@ def processData(data, args):
                         ):
                             blockRange = 1
                             highwayType = 11
-                        elif element["tags"]["highway"] == "motorway":
-                            blockRange = 4
+                            median = False
                         elif element["tags"]["highway"] == "track":
                             blockRange = 1
                             highwayType = 12
-                        elif (
-                            "lanes" in element["tags"]
-                            and element["tags"]["lanes"] != "1"
-                            and element["tags"]["lanes"] != "2"
-                        ):
-                            blockRange = 4
+                            median = False
 
                         for i in bresenham(
                             coordinate[0],
@@ -306,8 +396,31 @@ def processData(data, args):
                                         and img[y][x] == 0
                                     ):
                                         img[y][x] = highwayType
+                        if median:
+                            # Along the center line, over the road surface only
+                            for i in bresenham(
+                                coordinate[0],
+                                coordinate[1],
+                                previousElement[0],
+                                previousElement[1],
+                            ):
+                                if img[i[1]][i[0]] == highwayType:
+                                    img[i[1]][i[0]] = medianCode
                     previousElement = (coordinate[0], coordinate[1])
 
+                # Pedestrian squares and similar are mapped as area=yes highways
+                if isArea(element):
+                    nodesLen = len(element["nodes"])
+                    img = fillBudget.fill(
+                        img,
+                        round(sum(node[1] for node in element["nodes"]) / nodesLen),
+                        round(sum(node[0] for node in element["nodes"]) / nodesLen),
+                        highwayType,
+                        np.array([[0, 0]] + [node[:2] for node in element["nodes"]]),
+                        minMaxDistX,
+                        minMaxDistY,
+                    )
+
             elif "landuse" in element["tags"]:
                 previousElement = (0, 0)
                 cornerAddup = (0, 0, 0)
@@ -353,8 +466,8 @@ def processData(data, args):
                         )
                     previousElement = (coordinate[0], coordinate[1])
 
-                if cornerAddup != (0, 0, 0):
-                    imgLanduse = floodFill(
+                if cornerAddup != (0, 0, 0) and isArea(element):
+                    imgLanduse = fillBudget.fill(
                         imgLanduse,
                         round(cornerAddup[1] / cornerAddup[2]),
                         round(cornerAddup[0] / cornerAddup[2]),
@@ -390,6 +503,11 @@ def processData(data, args):
                             naturalType = 35
                         elif element["tags"]["natural"] == "water":
                             naturalType = 38
+                        elif (
+                            element["tags"]["natural"] == "bare_rock"
+                            or element["tags"]["natural"] == "scree"
+                        ):
+                            naturalType = bareRockCode
 
                         for i in bresenham(
                             coordinate[0],
@@ -410,9 +528,9 @@ def processData(data, args):
                         )
                     previousElement = (coordinate[0], coordinate[1])
 
-                if cornerAddup != (0, 0, 0):
+                if cornerAddup != (0, 0, 0) and isArea(element):
                     if naturalType != 32:
-                        imgLanduse = floodFill(
+                        imgLanduse = fillBudget.fill(
                             imgLanduse,
                             round(cornerAddup[1] / cornerAddup[2]),
                             round(cornerAddup[0] / cornerAddup[2]),
@@ -422,7 +540,7 @@ def processData(data, args):
                             minMaxDistY,
                         )
                     else:
-                        imgLanduse = floodFill(
+                        imgLanduse = fillBudget.fill(
                             imgLanduse,
                             round(cornerAddup[1] / cornerAddup[2]),
                             round(cornerAddup[0] / cornerAddup[2]),
@@ -473,8 +591,8 @@ def processData(data, args):
                         )
                     previousElement = (coordinate[0], coordinate[1])
 
-                if cornerAddup != (0, 0, 0):
-                    imgLanduse = floodFill(
+                if cornerAddup != (0, 0, 0) and isArea(element):
+                    imgLanduse = fillBudget.fill(
                         imgLanduse,
                         round(cornerAddup[1] / cornerAddup[2]),
                         round(cornerAddup[0] / cornerAddup[2]),
@@ -560,8 +678,8 @@ def processData(data, args):
 
                 if amenityType == 21:
                     amenityType = 37
-                if cornerAddup != (0, 0, 0):
-                    imgLanduse = floodFill(
+                if cornerAddup != (0, 0, 0) and isArea(element):
+                    imgLanduse = fillBudget.fill(
                         imgLanduse,
                         round(cornerAddup[1] / cornerAddup[2]),
                         round(cornerAddup[0] / cornerAddup[2]),
@@ -607,6 +725,8 @@ def processData(data, args):
                         ):
                             if i[0] < minMaxDistX and i[1] < minMaxDistY:
                                 img[i[1]][i[0]] = 14
+                        if args.minecarts and element["tags"]["railway"] == "rail":
+                            drawTrack(img, coordinate, previousElement)
                     previousElement = (coordinate[0], coordinate[1])
 
             elif "barrier" in element["tags"]:
@@ -640,16 +760,86 @@ def processData(data, args):
 
             ElementIncr += 1
 
+    if fillBudget.truncated > 0:
+        print(f"Warning! {fillBudget.truncated} areas hit the floodfill timeout")
+
+    img = drawSidewalks(img, data)
+    img = paintRoadMarkings(img, data)
+    img = paintCrossings(img, data, points, georeference)
+
+    coastlines = [
+        element["nodes"]
+        for element in data["elements"]
+        if element["type"] == "way"
+        and element.get("tags", {}).get("natural") == "coastline"
+    ]
+    if len(coastlines) > 0:
+        print("Filling ocean...")
+        ocean = np.zeros(imgLanduse.shape[:2], bool)
+        for polygon in getOceanPolygons(coastlines, minMaxDistX, minMaxDistY):
+            ocean |= polygonMask(imgLanduse.shape, polygon)
+        for polygon in getIslandPolygons(coastlines):
+            ocean &= ~polygonMask(imgLanduse.shape, polygon)
+
+        # Cut off or reversed coastlines flood the land instead of the sea
+        buildings = (img[:, :, 0] >= 50) & (img[:, :, 0] <= 89)
+        if np.count_nonzero(ocean & buildings) > np.count_nonzero(buildings) / 2:
+            print("Warning! Coastlines look inverted, swapping land and sea")
+            ocean = ~ocean
+        imgLanduse[ocean & (imgLanduse[:, :, 0] == 0)] = 40
+
+    if args.landcover is not None:
+        print("Sampling landcover...")
+        landcover = sampleLandcover(args.landcover, georeference, imgLanduse.shape)
+        fallback = (imgLanduse[:, :, 0] == 0) & (landcover != 0)
+        imgLanduse[fallback, 0] = landcover[fallback]
+
     print("Calculating layers...")
     for x in range(0, img.shape[0]):
         for y in range(0, img.shape[1]):
             if imgLanduse[x][y] != 0 and img[x][y] == 0:
                 img[x][y] = imgLanduse[x][y]
 
+    img = placeRocks(img, points, georeference)
+    img = placeViewpoints(img, points, georeference)
+    img = drawPowerLines(img, data, points, georeference)
+    img = placeStreetLamps(img, data, points, georeference)
+
+    if args.minecarts:
+        img = markStations(img, data)
+
+    if args.ferries is not None:
+        img = drawFerries(img, data)
+
+    if args.promenade:
+        img = addPromenade(img)
+
+    if args.imagery is not None:
+        print("Sampling aerial imagery...")
+        tint = sampleImagery(
+            args.imagery,
+            args.imageryZoom,
+            georeference,
+            img.shape,
+            args.downloadWorkers,
+        )
+        ground = (img[:, :, 0] == 0) & (tint != 0)
+        img[ground, 0] = tint[ground]
+
+    if args.gpx is not None:
+        print("Drawing GPX tracks...")
+        img = drawGpx(img, args.gpx, georeference)
+
+    if args.boundary is not None:
+        img = maskOutsideBoundary(img, getBoundaryPolygon(data))
+
+    if args.exportRasters is not None:
+        exportRasters(args.exportRasters, img, georeference)
+
     print(
         f"Processing finished in {(time() - processingStartTime):.2f} seconds"
         + f"({((time() - processingStartTime) / 60):.2f} minutes)"
     )
     if args.debug:
         imwrite("arnis-debug-map.png", img)
-    return np.flip(img, axis=1)
+    return np.flip(img, axis=1), np.flip(imgAge, axis=1)
@@ -0,0 +1,61 @@
+import time
+
+
+def formatDuration(seconds):
+    seconds = round(seconds)
+    if seconds < 60:
+        return f"{seconds}s"
+    minutes, seconds = divmod(seconds, 60)
+    if minutes < 60:
+        return f"{minutes}m {seconds:02d}s"
+    hours, minutes = divmod(minutes, 60)
+    return f"{hours}h {minutes:02d}m"
+
+
+class Progress:
+    # Prints every 10 percent, with the remaining time extrapolated from the
+    # throughput so far
+    def __init__(self, label, total):
+        self.label = label
+        self.total = total
+        self.start = time.time()
+        self.lastPercentage = 0
+
+    def update(self, done):
+        percentage = round(100 * done / self.total)
+        if percentage % 10 != 0 or percentage == self.lastPercentage:
+            return
+        self.lastPercentage = percentage
+
+        elapsed = time.time() - self.start
+        message = f"{self.label} {done}/{self.total} ({percentage}%)"
+        message += f", {formatDuration(elapsed)} elapsed"
+        if 0 < done < self.total:
+            remaining = elapsed * (self.total - done) / done
+            message += f", about {formatDuration(remaining)} left"
+        print(message)
+
+
+class StageTimer:
+    # Stages follow each other without gaps, starting one ends the previous one
+    def __init__(self):
+        self.durations = {}
+        self.stage = None
+        self.stageStart = 0
+
+    def startStage(self, name):
+        self.endStage()
+        self.stage = name
+        self.stageStart = time.perf_counter()
+
+    def endStage(self):
+        if self.stage is not None:
+            duration = time.perf_counter() - self.stageStart
+            self.durations[self.stage] = self.durations.get(self.stage, 0) + duration
+            self.stage = None
+
+    def summary(self):
+        return ", ".join(
+            f"{name} {formatDuration(duration)}"
+            for name, duration in self.durations.items()
+        )
@@ -0,0 +1,32 @@
+import numpy as np
+
+promenadeCode = 17
+railingCode = 18
+promenadeWidth = 3
+waterCodes = (38, 40)  # Water, ocean
+
+
+def nextTo(mask):
+    result = np.zeros_like(mask)
+    result[1:, :] |= mask[:-1, :]
+    result[:-1, :] |= mask[1:, :]
+    result[:, 1:] |= mask[:, :-1]
+    result[:, :-1] |= mask[:, 1:]
+    return result
+
+
+def addPromenade(img):
+    # Built-up landuse is left as plain ground, so only ground along the water is
+    # turned into a promenade, never parks, beaches or fields
+    water = np.isin(img[:, :, 0], waterCodes)
+    ground = img[:, :, 0] == 0
+
+    nearWater = water
+    for _ in range(promenadeWidth):
+        nearWater = nearWater | nextTo(nearWater)
+    railing = ground & nextTo(water)
+    promenade = ground & nearWater & ~railing
+
+    img[promenade, 0] = promenadeCode
+    img[railing, 0] = railingCode
+    return img
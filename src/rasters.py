@@ -0,0 +1,42 @@
+from cv2 import imwrite
+import numpy as np
+
+groundHeight = 1
+
+
+def surfaceHeight(codes):
+    # Highest generated block per column, building codes end with the floor count
+    heights = np.full(codes.shape, groundHeight, np.uint16)
+    buildings = (codes >= 50) & (codes <= 89)
+    heights[buildings] = 5 + 3 * (codes[buildings] % 10)
+    return heights
+
+
+def worldFile(georeference, width):
+    # North up rows along z, columns along x, values are pixel centers
+    pixelLon = georeference.longitude(1) - georeference.longitude(0)
+    pixelLat = georeference.latitude(1) - georeference.latitude(0)
+    lines = [
+        pixelLon,
+        0,
+        0,
+        -pixelLat,
+        georeference.longitude(0),
+        georeference.latitude(width - 1),
+    ]
+    return "\n".join(f"{value:.10f}" for value in lines) + "\n"
+
+
+def exportRasters(prefix, img, georeference):
+    # The image is indexed [x][latitude], so transposing and flipping it gives
+    # the [z][x] layout of the generated world with north at the top
+    codes = np.flip(img[:, :, 0].T, axis=0)
+    world = worldFile(georeference, img.shape[1])
+    for name, raster in (
+        ("landuse", codes.astype(np.uint8)),
+        ("heightmap", surfaceHeight(codes)),
+    ):
+        imwrite(f"{prefix}-{name}.png", raster)
+        with open(f"{prefix}-{name}.pgw", "w", encoding="utf-8") as f:
+            f.write(world)
+    print(f"Exported rasters {prefix}-landuse.png and {prefix}-heightmap.png")
@@ -0,0 +1,84 @@
+import os
+import time
+import socket
+import struct
+import numpy as np
+
+loginType = 3
+commandType = 2
+# Vanilla refuses to forceload more than 256 chunks at once
+batchSize = 256
+
+
+class RconClient:
+    def __init__(self, host, port, password):
+        self.socket = socket.create_connection((host, port), timeout=30)
+        self.requestId = 0
+        if self.request(loginType, password) is None:
+            print("Error! RCON login failed, check the password")
+            os._exit(1)
+
+    def receive(self, length):
+        data = b""
+        while len(data) < length:
+            block = self.socket.recv(length - len(data))
+            if not block:
+                raise ConnectionError("RCON connection closed")
+            data += block
+        return data
+
+    def request(self, requestType, payload):
+        self.requestId += 1
+        body = struct.pack("<ii", self.requestId, requestType)
+        body += payload.encode("utf-8") + b"\x00\x00"
+        self.socket.sendall(struct.pack("<i", len(body)) + body)
+
+        length = struct.unpack("<i", self.receive(4))[0]
+        responseId, _ = struct.unpack("<ii", self.receive(8))
+        response = self.receive(length - 8)[:-2].decode("utf-8")
+        # A failed login is answered with the request id -1
+        return None if responseId == -1 else response
+
+    def command(self, command):
+        return self.request(commandType, command)
+
+    def close(self):
+        self.socket.close()
+
+
+def rowCommands(recorder, startX, endX, startZ, endZ):
+    states = {index: state for state, index in recorder.palette.items()}
+    height = recorder.blocks.shape[0]
+    for y in range(height):
+        for z in range(startZ, endZ):
+            row = recorder.blocks[y, z, startX:endX]
+            # Runs of the same block become a single fill command
+            starts = np.concatenate(([0], np.nonzero(np.diff(row))[0] + 1))
+            ends = np.concatenate((starts[1:], [len(row)]))
+            for start, end in zip(starts, ends):
+                if row[start] == 0:
+                    continue
+                x1, x2 = startX + start, startX + end - 1
+                yield f"fill {x1} {y} {z} {x2} {y} {z} {states[row[start]]}"
+
+
+def streamToServer(recorder, address, password, commandsPerSecond):
+    host, _, port = address.partition(":")
+    client = RconClient(host, int(port or 25575), password)
+    print(f"Streaming blocks to {address}...")
+
+    height, length, width = recorder.blocks.shape
+    sent = 0
+    for startX in range(0, width, batchSize):
+        for startZ in range(0, length, batchSize):
+            endX = min(startX + batchSize, width)
+            endZ = min(startZ + batchSize, length)
+            # Blocks can only be placed in loaded chunks
+            client.command(f"forceload add {startX} {startZ} {endX - 1} {endZ - 1}")
+            for command in rowCommands(recorder, startX, endX, startZ, endZ):
+                client.command(command)
+                sent += 1
+                time.sleep(1 / commandsPerSecond)
+            client.command(f"forceload remove {startX} {startZ} {endX - 1} {endZ - 1}")
+            print(f"Streamed {sent} commands")
+    client.close()
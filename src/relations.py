@@ -0,0 +1,134 @@
+from collections import Counter
+
+supportedRelations = {"multipolygon"}
+# Far below the ids of the boundary polygon and of the building footprints,
+# which count down from -1 and from -10**9
+relationWayIdOffset = -(10**12)
+
+
+def assembleRings(ways):
+    # Joins member ways in either direction, returns (closed rings, open chains)
+    chains = [list(way) for way in ways if len(way) >= 2]
+    rings = []
+    while chains:
+        chain = chains.pop(0)
+        extended = True
+        while chain[0] != chain[-1] and extended:
+            extended = False
+            for i, other in enumerate(chains):
+                if other[0] == chain[-1]:
+                    chain += other[1:]
+                elif other[-1] == chain[-1]:
+                    chain += other[-2::-1]
+                else:
+                    continue
+                del chains[i]
+                extended = True
+                break
+        if chain[0] == chain[-1] and len(chain) >= 4:
+            rings.append(chain)
+        else:
+            return rings, chains + [chain]
+    return rings, []
+
+
+def pointInRing(point, ring):
+    # Even-odd rule, the ring is closed and points are (lat, lon)
+    inside = False
+    for (x0, y0), (x1, y1) in zip(ring, ring[1:]):
+        if (y0 > point[1]) != (y1 > point[1]):
+            if point[0] < x0 + (point[1] - y0) * (x1 - x0) / (y1 - y0):
+                inside = not inside
+    return inside
+
+
+def ringAround(point, rings, coordinates):
+    # Index of the ring around a (lat, lon) point, None if there is none
+    for i, ring in enumerate(rings):
+        points = [coordinates.get(node) for node in ring]
+        if point is not None and None not in points and pointInRing(point, points):
+            return i
+    return None
+
+
+def cutHoles(ring, holes):
+    # Every hole is appended to the outer ring, returning to its start after
+    # each one. The connecting lines are walked in both directions, so the
+    # even-odd scanline fill leaves the holes empty and their outlines stop
+    # the floodfill.
+    nodes = list(ring)
+    for hole in holes:
+        nodes += hole + [ring[0]]
+    return nodes
+
+
+def classifyRelation(relation):
+    return relation.get("tags", {}).get("type", "untyped")
+
+
+def assembleMultipolygons(data):
+    # Outer rings with their holes become ways carrying the relation tags,
+    # incomplete relations are skipped entirely instead of drawing half of
+    # their outline.
+    # Returns the number of added ways and the skipped relations by type.
+    ways = {
+        element["id"]: element["nodes"]
+        for element in data["elements"]
+        if element["type"] == "way"
+    }
+    coordinates = {
+        element["id"]: (element["lat"], element["lon"])
+        for element in data["elements"]
+        if element["type"] == "node"
+    }
+    added = []
+    skipped = Counter()
+    nextId = relationWayIdOffset
+    for relation in data["elements"]:
+        if relation["type"] != "relation":
+            continue
+        relationType = classifyRelation(relation)
+        if relationType not in supportedRelations:
+            skipped[relationType] += 1
+            continue
+
+        members = relation.get("members", [])
+        outer = [
+            ways.get(member["ref"])
+            for member in members
+            if member["type"] == "way" and member.get("role", "outer") in ("outer", "")
+        ]
+        inner = [
+            ways.get(member["ref"])
+            for member in members
+            if member["type"] == "way" and member.get("role") == "inner"
+        ]
+        if not outer or None in outer or None in inner:
+            skipped["multipolygon (incomplete)"] += 1
+            continue
+        rings, unclosed = assembleRings(outer)
+        holes, unclosedHoles = assembleRings(inner)
+        if unclosed or unclosedHoles:
+            skipped["multipolygon (unclosed)"] += 1
+            continue
+
+        holesPerRing = [[] for _ in rings]
+        for hole in holes:
+            # Each hole belongs to the outer ring around its first node
+            i = ringAround(coordinates.get(hole[0]), rings, coordinates)
+            if i is not None:
+                holesPerRing[i].append(hole)
+
+        for ring, ringHoles in zip(rings, holesPerRing):
+            added.append(
+                {
+                    "type": "way",
+                    "id": nextId,
+                    "nodes": cutHoles(ring, ringHoles),
+                    "tags": relation.get("tags", {}),
+                }
+            )
+            nextId -= 1
+
+    data["elements"].extend(added)
+    return len(added), skipped
@@ -0,0 +1,97 @@
+from math import hypot
+
+from .bresenham import bresenham
+from .roadWidth import parseLanes, roadBlockRange, roadLanes
+from .sidewalks import isRoad, leftNormal
+
+laneDividerCode = 19  # Street markings, white
+centerLineCode = 96  # Yellow
+# Roads without a lanes tag only get markings from these classes on
+markedRoads = ("motorway", "trunk", "primary", "secondary", "tertiary")
+# Painted and unpainted blocks of dashed lane dividers
+dashLength = 4
+# Offsets of sharp bends are limited, instead of reaching far outside
+maxMiter = 2
+
+
+def isMarked(tags):
+    if not ("lanes" in tags or tags.get("highway") in markedRoads):
+        return False
+    return roadLanes(tags) >= 2
+
+
+def isOneway(tags):
+    return tags.get("oneway") in ("yes", "-1") or tags.get("highway") == "motorway"
+
+
+def laneLines(tags):
+    # (offset to the left of the center line, code, dashed) of every line
+    # between two lanes, the lanes of the opposite direction are on the left
+    lanes = roadLanes(tags)
+    width = 2 * roadBlockRange(tags) + 1
+    backward = parseLanes(tags.get("lanes:backward")) or lanes // 2
+    lines = []
+    for lane in range(1, lanes):
+        offset = width / 2 - lane * width / lanes
+        if not isOneway(tags) and lane == backward:
+            lines.append((offset, centerLineCode, False))
+        else:
+            lines.append((offset, laneDividerCode, True))
+    return lines
+
+
+def vertexOffsets(nodes):
+    # Left normal at every node, the average of both segments at bends and
+    # lengthened so lines keep their distance from the center line
+    normals = [leftNormal(start, end) for start, end in zip(nodes, nodes[1:])]
+    offsets = []
+    for index in range(len(nodes)):
+        adjacent = [
+            normals[segment]
+            for segment in (index - 1, index)
+            if 0 <= segment < len(normals) and normals[segment] is not None
+        ]
+        if not adjacent:
+            offsets.append((0, 0))
+            continue
+        sumX = sum(normal[0] for normal in adjacent)
+        sumY = sum(normal[1] for normal in adjacent)
+        length = hypot(sumX, sumY)
+        if length == 0:  # Reversing, keep the normal of the first segment
+            offsets.append(adjacent[0])
+            continue
+        averageX, averageY = sumX / length, sumY / length
+        cosine = averageX * adjacent[0][0] + averageY * adjacent[0][1]
+        miter = min(1 / cosine, maxMiter)
+        offsets.append((averageX * miter, averageY * miter))
+    return offsets
+
+
+def paintLine(img, nodes, offsets, distance, code, dashed):
+    travelled = 0
+    for index, (start, end) in enumerate(zip(nodes, nodes[1:])):
+        startOffset, endOffset = offsets[index], offsets[index + 1]
+        pixels = list(bresenham(start[0], start[1], end[0], end[1]))
+        for step, (x, y) in enumerate(pixels):
+            # Offsets blend between both nodes, so the line follows curves
+            t = step / max(len(pixels) - 1, 1)
+            offsetX = startOffset[0] + (endOffset[0] - startOffset[0]) * t
+            offsetY = startOffset[1] + (endOffset[1] - startOffset[1]) * t
+            travelled += 1
+            if dashed and (travelled // dashLength) % 2 == 1:
+                continue
+            lineX = round(x + offsetX * distance)
+            lineY = round(y + offsetY * distance)
+            if 0 <= lineY < img.shape[0] and 0 <= lineX < img.shape[1]:
+                if img[lineY][lineX] == 10:  # Street
+                    img[lineY][lineX] = code
+
+
+def paintRoadMarkings(img, data):
+    for element in data["elements"]:
+        if not isRoad(element) or not isMarked(element["tags"]):
+            continue
+        offsets = vertexOffsets(element["nodes"])
+        for distance, code, dashed in laneLines(element["tags"]):
+            paintLine(img, element["nodes"], offsets, distance, code, dashed)
+    return img
@@ -0,0 +1,52 @@
+laneWidth = 3  # Blocks, one block is about one meter
+# Lanes of roads without a lanes or width tag, everything else has two
+defaultLanes = {"motorway": 3, "service": 1, "living_street": 1}
+# Blocks to each side of the center line of the widest roads
+maxBlockRange = 8
+medianCode = 97
+
+
+def parseWidth(value):
+    # "7", "7.5 m" or "7,5", other units are ignored
+    if value is None:
+        return None
+    value = value.strip().replace(",", ".")
+    if value.endswith("m"):
+        value = value[:-1].strip()
+    try:
+        width = float(value)
+    except ValueError:
+        return None
+    return width if width > 0 else None
+
+
+def parseLanes(value):
+    # Lanes of both directions, e.g. "4" or "2;3" for varying lanes
+    try:
+        lanes = max(int(lane) for lane in value.split(";"))
+    except (AttributeError, ValueError):
+        return None
+    return lanes if lanes > 0 else None
+
+
+def roadLanes(tags):
+    lanes = parseLanes(tags.get("lanes"))
+    if lanes is None:
+        lanes = defaultLanes.get(tags.get("highway"), 2)
+    return lanes
+
+
+def roadBlockRange(tags):
+    # Blocks to each side of the center line, the road is 2 * range + 1 wide
+    width = parseWidth(tags.get("width"))
+    if width is None:
+        width = roadLanes(tags) * laneWidth
+    return min(max(round((width - 1) / 2), 1), maxBlockRange)
+
+
+def hasMedian(tags):
+    # Dual carriageways mapped as a single way, both directions get separated
+    if tags.get("dual_carriageway") == "yes":
+        return True
+    lanes = parseLanes(tags.get("lanes"))
+    return lanes is not None and lanes >= 4 and tags.get("oneway") not in ("yes", "-1")
@@ -0,0 +1,14 @@
+boulderCode = 25
+bareRockCode = 26
+# Boulders are only placed on open ground, never on roads, water or buildings
+rockGround = (0, bareRockCode, 30, 31, 32, 33, 34, 35, 36, 39)
+
+
+def placeRocks(img, points, georeference):
+    for tags, lat, lon in points:
+        if tags.get("natural") not in ("rock", "stone"):
+            continue
+        x, y = georeference.toPixel(lat, lon)
+        if 0 <= y < img.shape[0] and 0 <= x < img.shape[1] and img[y][x] in rockGround:
+            img[y][x] = boulderCode
+    return img
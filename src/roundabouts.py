@@ -0,0 +1,36 @@
+from math import ceil, floor, hypot
+
+from .areaDetection import isClosed
+
+islandCode = 30  # Meadow
+
+
+def isRoundabout(element):
+    # Roundabouts split into several ways are drawn along their nodes instead
+    tags = element.get("tags", {})
+    return tags.get("junction") == "roundabout" and isClosed(element["nodes"])
+
+
+def roundaboutCircle(nodes):
+    # The closing node repeats the first one
+    ring = nodes[:-1]
+    centerX = sum(node[0] for node in ring) / len(ring)
+    centerY = sum(node[1] for node in ring) / len(ring)
+    radius = sum(hypot(node[0] - centerX, node[1] - centerY) for node in ring)
+    return centerX, centerY, radius / len(ring)
+
+
+def drawRoundabout(img, nodes, blockRange, highwayType=10):
+    # A circular carriageway as wide as the road, around a grass island
+    centerX, centerY, radius = roundaboutCircle(nodes)
+    outer = radius + blockRange
+    for y in range(max(floor(centerY - outer), 0), ceil(centerY + outer) + 1):
+        for x in range(max(floor(centerX - outer), 0), ceil(centerX + outer) + 1):
+            if y >= img.shape[0] or x >= img.shape[1] or img[y][x] != 0:
+                continue
+            distance = hypot(x - centerX, y - centerY)
+            if abs(distance - radius) <= blockRange:
+                img[y][x] = highwayType
+            elif distance < radius:
+                img[y][x] = islandCode
+    return img
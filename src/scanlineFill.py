@@ -0,0 +1,55 @@
+from math import ceil, floor
+import numpy as np
+
+from .floodFill import findSeed
+
+
+def rowCrossings(polygon, row):
+    # Columns where the outline crosses the row, each edge counts for the rows
+    # from its lower end up to (excluding) its upper end
+    crossings = []
+    for (col0, row0), (col1, row1) in zip(polygon, polygon[1:] + polygon[:1]):
+        if row0 == row1 or not (min(row0, row1) <= row < max(row0, row1)):
+            continue
+        crossings.append(col0 + (row - row0) * (col1 - col0) / (row1 - row0))
+    return sorted(crossings)
+
+
+def scanlineFill(
+    img,
+    px,
+    py,
+    newColor,
+    currentBuilding,
+    minMaxDistX,
+    minMaxDistY,
+    elementType="None",
+):
+    # Fills every pixel of the start point's color inside the outline, using
+    # the even-odd rule, so self-intersecting outlines can't leak. Returns the
+    # number of filled pixels, None if no start point is found.
+    currentBuilding = np.delete(currentBuilding, 0, axis=0)
+    seed = findSeed(img, px, py, currentBuilding, minMaxDistX, minMaxDistY)
+    if seed is None:
+        return None
+    oldColor = img[seed[0]][seed[1]][0]
+    filled = 0
+
+    polygon = [(float(vertex[0]), float(vertex[1])) for vertex in currentBuilding]
+    firstRow = max(ceil(min(vertex[1] for vertex in polygon)), 0)
+    lastRow = min(floor(max(vertex[1] for vertex in polygon)), img.shape[0] - 1)
+    for row in range(firstRow, lastRow + 1):
+        crossings = rowCrossings(polygon, row)
+        for start, end in zip(crossings[::2], crossings[1::2]):
+            first = max(ceil(start), 0)
+            last = min(floor(end), img.shape[1] - 1)
+            if first > last:
+                continue
+            values = img[row, first : last + 1, 0]
+            fill = values == oldColor
+            if elementType == "building":
+                # Buildings are built over roads running into them
+                fill |= (values >= 10) & (values <= 19)
+            values[fill] = newColor
+            filled += int(np.count_nonzero(fill))
+    return filled
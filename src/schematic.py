@@ -0,0 +1,38 @@
+import numpy as np
+from nbt import nbt
+
+dataVersion = 3120  # Minecraft 1.19.2, like the datapack written by pregen
+
+
+def encodeVarints(values):
+    # Palette indices are below 16384, so every varint has one or two bytes
+    extra = values >= 128
+    shift = np.concatenate(([0], np.cumsum(extra)[:-1]))
+    positions = np.arange(len(values)) + shift
+    data = np.zeros(len(values) + np.count_nonzero(extra), np.uint8)
+    data[positions] = (values & 0x7F) | (extra * 0x80)
+    data[positions[extra] + 1] = values[extra] >> 7
+    return data
+
+
+def saveSchematic(recorder, path):
+    height, length, width = recorder.blocks.shape
+    schematic = nbt.NBTFile()
+    schematic.name = "Schematic"
+    schematic.tags.append(nbt.TAG_Int(name="Version", value=2))
+    schematic.tags.append(nbt.TAG_Int(name="DataVersion", value=dataVersion))
+    schematic.tags.append(nbt.TAG_Short(name="Width", value=width))
+    schematic.tags.append(nbt.TAG_Short(name="Height", value=height))
+    schematic.tags.append(nbt.TAG_Short(name="Length", value=length))
+
+    palette = nbt.TAG_Compound(name="Palette")
+    for state, index in recorder.palette.items():
+        palette.tags.append(nbt.TAG_Int(name=state, value=index))
+    schematic.tags.append(palette)
+    schematic.tags.append(nbt.TAG_Int(name="PaletteMax", value=len(recorder.palette)))
+
+    blockData = nbt.TAG_Byte_Array(name="BlockData")
+    blockData.value = bytearray(encodeVarints(recorder.blocks.ravel()).tobytes())
+    schematic.tags.append(blockData)
+    schematic.write_file(path)
+    print(f"Saved schematic {path}")
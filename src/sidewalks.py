@@ -0,0 +1,172 @@
+from math import hypot
+
+from .bresenham import bresenham
+from .roadWidth import roadBlockRange
+from .roundabouts import isRoundabout
+from .spatialIndex import GridIndex
+
+sidewalkCode = 11  # Footway
+crossingCode = 19  # Street markings
+# Street and its markings, see roadMarkings
+roadSurface = (10, 19, 96)
+sidewalkWidth = 2
+# Roads which usually have sidewalks on both sides if nothing else is mapped
+impliedSidewalks = ("primary", "secondary", "tertiary", "residential")
+# Values of sidewalk=* and sidewalk:both=*, "no" and "separate" have none
+sidewalkValues = {
+    "both": {"left", "right"},
+    "yes": {"left", "right"},
+    "left": {"left"},
+    "right": {"right"},
+}
+# Paths and tracks are drawn with their own width and never get markings
+roadExceptions = ("path", "footway", "track", "steps", "corridor", "bridge")
+# Zebra stripes along the road, to each side of the crossing node
+crossingHalfLength = 1
+# Crossing nodes further away from any road are skipped
+crossingSearchRadius = 3
+# Pixels, a few road segments per cell in cities
+segmentCellSize = 32
+
+
+def sidewalkSides(tags):
+    for key in ("sidewalk", "sidewalk:both"):
+        if key in tags:
+            return sidewalkValues.get(tags[key], set())
+    # sidewalk:left=yes and sidewalk:right=yes on their own
+    tagged = {side for side in ("left", "right") if f"sidewalk:{side}" in tags}
+    if tagged:
+        return {side for side in tagged if tags[f"sidewalk:{side}"] == "yes"}
+    if tags.get("highway") in impliedSidewalks:
+        return {"left", "right"}
+    return set()
+
+
+def isRoad(element):
+    if element["type"] != "way" or isRoundabout(element):
+        return False
+    highway = element.get("tags", {}).get("highway")
+    return highway is not None and highway not in roadExceptions
+
+
+def leftNormal(start, end):
+    # Nodes are (north, east) pixels, turning (north, east) to the left of
+    # the way gives (east, -north)
+    length = hypot(end[0] - start[0], end[1] - start[1])
+    if length == 0:
+        return None
+    return (end[1] - start[1]) / length, -(end[0] - start[0]) / length
+
+
+def setIfEmpty(img, x, y, code):
+    if 0 <= y < img.shape[0] and 0 <= x < img.shape[1] and img[y][x] == 0:
+        img[y][x] = code
+
+
+def drawSidewalks(img, data):
+    # After all elements, so sidewalks never cut through other roads
+    for element in data["elements"]:
+        if not isRoad(element):
+            continue
+        sides = sidewalkSides(element["tags"])
+        if not sides:
+            continue
+        blockRange = roadBlockRange(element["tags"])
+        # Half steps close the gaps next to diagonal roads
+        offsets = [
+            blockRange + 1 + step / 2 for step in range(0, sidewalkWidth * 2 - 1)
+        ]
+        for start, end in zip(element["nodes"], element["nodes"][1:]):
+            normal = leftNormal(start, end)
+            if normal is None:
+                continue
+            for x, y in bresenham(start[0], start[1], end[0], end[1]):
+                for side in sides:
+                    direction = 1 if side == "left" else -1
+                    for offset in offsets:
+                        setIfEmpty(
+                            img,
+                            round(x + normal[0] * offset * direction),
+                            round(y + normal[1] * offset * direction),
+                            sidewalkCode,
+                        )
+    return img
+
+
+def segmentDistance(point, start, end):
+    lengthSquared = (end[0] - start[0]) ** 2 + (end[1] - start[1]) ** 2
+    if lengthSquared == 0:
+        return hypot(point[0] - start[0], point[1] - start[1])
+    t = (
+        (point[0] - start[0]) * (end[0] - start[0])
+        + (point[1] - start[1]) * (end[1] - start[1])
+    ) / lengthSquared
+    t = min(max(t, 0), 1)
+    return hypot(
+        point[0] - start[0] - t * (end[0] - start[0]),
+        point[1] - start[1] - t * (end[1] - start[1]),
+    )
+
+
+def indexRoadSegments(data):
+    segments = GridIndex(segmentCellSize)
+    for element in data["elements"]:
+        if not isRoad(element):
+            continue
+        for start, end in zip(element["nodes"], element["nodes"][1:]):
+            box = (
+                min(start[0], end[0]),
+                min(start[1], end[1]),
+                max(start[0], end[0]),
+                max(start[1], end[1]),
+            )
+            segments.insert((element["tags"], start, end), box)
+    return segments
+
+
+def nearestRoadSegment(segments, point):
+    # Returns the tags and the segment of the closest road within the radius
+    nearest = None
+    box = (
+        point[0] - crossingSearchRadius,
+        point[1] - crossingSearchRadius,
+        point[0] + crossingSearchRadius,
+        point[1] + crossingSearchRadius,
+    )
+    for tags, start, end in segments.query(box):
+        distance = segmentDistance(point, start, end)
+        if distance <= crossingSearchRadius and (
+            nearest is None or distance < nearest[0]
+        ):
+            nearest = (distance, tags, start, end)
+    return None if nearest is None else nearest[1:]
+
+
+def paintCrossings(img, data, points, georeference):
+    segments = indexRoadSegments(data)
+    for tags, lat, lon in points:
+        if tags.get("highway") != "crossing":
+            continue
+        if tags.get("crossing") in ("unmarked", "no"):
+            continue
+        point = georeference.toPixel(lat, lon)
+        road = nearestRoadSegment(segments, point)
+        if road is None:
+            continue
+        roadTags, start, end = road
+        normal = leftNormal(start, end)
+        if normal is None:
+            continue
+        # Stripes along the road, alternating with the road surface across it
+        alongX, alongY = -normal[1], normal[0]
+        blockRange = roadBlockRange(roadTags)
+        for along in range(-crossingHalfLength, crossingHalfLength + 1):
+            for across in range(-blockRange, blockRange + 1):
+                x = round(point[0] + alongX * along + normal[0] * across)
+                y = round(point[1] + alongY * along + normal[1] * across)
+                if 0 <= y < img.shape[0] and 0 <= x < img.shape[1]:
+                    # Lane markings are interrupted by the crossing
+                    if img[y][x] in roadSurface:
+                        stripe = (across + blockRange) % 2 == 0
+                        img[y][x] = crossingCode if stripe else 10
+    return img
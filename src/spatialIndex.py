@@ -0,0 +1,37 @@
+from math import floor
+
+
+class GridIndex:
+    # Uniform grid over bounding boxes (min0, min1, max0, max1), answers box
+    # queries by looking at the covered cells only instead of every item
+    def __init__(self, cellSize):
+        self.cellSize = cellSize
+        self.cells = {}
+
+    def cellRange(self, box):
+        return (
+            range(floor(box[0] / self.cellSize), floor(box[2] / self.cellSize) + 1),
+            range(floor(box[1] / self.cellSize), floor(box[3] / self.cellSize) + 1),
+        )
+
+    def insert(self, item, box):
+        rows, cols = self.cellRange(box)
+        for row in rows:
+            for col in cols:
+                self.cells.setdefault((row, col), []).append((item, box))
+
+    def query(self, box):
+        # Items whose boxes intersect the box, each one only once
+        found = {}
+        rows, cols = self.cellRange(box)
+        for row in rows:
+            for col in cols:
+                for item, itemBox in self.cells.get((row, col), []):
+                    if (
+                        itemBox[0] <= box[2]
+                        and box[0] <= itemBox[2]
+                        and itemBox[1] <= box[3]
+                        and box[1] <= itemBox[3]
+                    ):
+                        found[id(item)] = item
+        return list(found.values())
@@ -0,0 +1,86 @@
+from math import hypot
+
+from .bresenham import bresenham
+from .roadWidth import roadBlockRange
+from .sidewalks import sidewalkWidth
+
+streetLampCode = 29
+# Lamps stand on sidewalks and open ground, never on the road itself
+lampGround = (0, 11, 30, 39)
+# Only used when the area has no mapped street lamps at all
+litRoads = ("residential", "living_street")
+lampSpacing = 16
+# Blocks further out to look for open ground, beyond the road and sidewalk
+roadsideSearch = 4
+
+
+def isLampGround(img, x, y):
+    return 0 <= y < img.shape[0] and 0 <= x < img.shape[1] and img[y][x] in lampGround
+
+
+def nearestLampGround(img, x, y, radius=3):
+    # Lamp nodes are often mapped on the edge of the road
+    candidates = [
+        (abs(offsetX) + abs(offsetY), x + offsetX, y + offsetY)
+        for offsetX in range(-radius, radius + 1)
+        for offsetY in range(-radius, radius + 1)
+        if isLampGround(img, x + offsetX, y + offsetY)
+    ]
+    if not candidates:
+        return None
+    _, x, y = min(candidates)
+    return x, y
+
+
+def placeMappedLamps(img, points, georeference):
+    placed = 0
+    for tags, lat, lon in points:
+        if tags.get("highway") != "street_lamp":
+            continue
+        position = nearestLampGround(img, *georeference.toPixel(lat, lon))
+        if position is not None:
+            img[position[1]][position[0]] = streetLampCode
+            placed += 1
+    return placed
+
+
+def placeRoadsideLamps(img, data):
+    # Every lampSpacing blocks along the road, alternating between both sides
+    for element in data["elements"]:
+        if element["type"] != "way":
+            continue
+        if element.get("tags", {}).get("highway") not in litRoads:
+            continue
+        # Right behind the sidewalk, also if the road has none
+        firstOffset = roadBlockRange(element["tags"]) + sidewalkWidth + 1
+        roadsideOffsets = range(firstOffset, firstOffset + roadsideSearch)
+        side = 1
+        travelled = lampSpacing // 2
+        previous = None
+        for node in element["nodes"]:
+            if previous is not None:
+                length = hypot(node[0] - previous[0], node[1] - previous[1])
+                if length == 0:
+                    continue
+                normalX = -(node[1] - previous[1]) / length
+                normalY = (node[0] - previous[0]) / length
+                for x, y in bresenham(previous[0], previous[1], node[0], node[1]):
+                    travelled += 1
+                    if travelled < lampSpacing:
+                        continue
+                    for offset in roadsideOffsets:
+                        lampX = round(x + normalX * offset * side)
+                        lampY = round(y + normalY * offset * side)
+                        if isLampGround(img, lampX, lampY):
+                            img[lampY][lampX] = streetLampCode
+                            travelled = 0
+                            side = -side
+                            break
+            previous = node
+    return img
+
+
+def placeStreetLamps(img, data, points, georeference):
+    if placeMappedLamps(img, points, georeference) == 0:
+        img = placeRoadsideLamps(img, data)
+    return img
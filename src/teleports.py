@@ -0,0 +1,115 @@
+import re
+import json
+import unicodedata
+
+from .markers import nodeBlockCoordinates, toBlockCoordinates
+from .pregen import createDatapack
+
+teleportY = 2
+warpsPerPage = 12
+poiKeys = ("amenity", "tourism", "historic", "leisure", "shop", "building", "place")
+
+
+def isPoi(tags):
+    if "name" not in tags:
+        return False
+    return tags.get("railway") in ("station", "halt") or any(
+        key in tags for key in poiKeys
+    )
+
+
+def functionName(name, elementId, used):
+    # Function names may only contain lowercase letters, digits and underscores
+    plain = unicodedata.normalize("NFKD", name).encode("ascii", "ignore").decode()
+    slug = re.sub(r"[^a-z0-9_]+", "_", plain.lower()).strip("_")
+    if slug == "" or slug in used:
+        slug = f"{slug}_{abs(elementId)}".strip("_")
+    used.add(slug)
+    return slug
+
+
+def collectTeleports(data, nodes, georeference, minMaxDistX, minMaxDistY):
+    teleports = []
+    used = set()
+    for element in data["elements"]:
+        if (
+            element["type"] != "way"
+            or len(element["nodes"]) == 0
+            or not isPoi(element.get("tags", {}))
+        ):
+            continue
+        points = [toBlockCoordinates(node, minMaxDistX) for node in element["nodes"]]
+        teleports.append(
+            {
+                "name": element["tags"]["name"],
+                "function": functionName(element["tags"]["name"], element["id"], used),
+                "x": round(sum(point[0] for point in points) / len(points)),
+                "z": round(sum(point[1] for point in points) / len(points)),
+            }
+        )
+    # Stations and places are mostly mapped as single nodes
+    for elementId, tags, lat, lon in nodes:
+        if not isPoi(tags):
+            continue
+        point = nodeBlockCoordinates(lat, lon, georeference, minMaxDistX, minMaxDistY)
+        if point is None:
+            continue
+        teleports.append(
+            {
+                "name": tags["name"],
+                "function": functionName(tags["name"], elementId, used),
+                "x": point[0],
+                "z": point[1],
+            }
+        )
+    return sorted(teleports, key=lambda teleport: teleport["name"])
+
+
+def warpEntry(teleport):
+    return {
+        "text": teleport["name"] + "\n",
+        "color": "dark_blue",
+        "clickEvent": {
+            "action": "run_command",
+            "value": f"/function arnis:tp_{teleport['function']}",
+        },
+    }
+
+
+def warpBookCommand(teleports):
+    pages = []
+    for start in range(0, len(teleports), warpsPerPage):
+        page = [""] + [
+            warpEntry(teleport) for teleport in teleports[start : start + warpsPerPage]
+        ]
+        # Pages are JSON text inside single quoted SNBT strings
+        text = json.dumps(page, ensure_ascii=False)
+        pages.append("'" + text.replace("\\", "\\\\").replace("'", "\\'") + "'")
+    return (
+        'give @s written_book{title:"Arnis warps",author:"Arnis",pages:['
+        + ",".join(pages)
+        + "]}"
+    )
+
+
+def writeTeleports(data, nodes, georeference, minMaxDistX, minMaxDistY, mcWorldPath):
+    teleports = collectTeleports(data, nodes, georeference, minMaxDistX, minMaxDistY)
+    functionPath = createDatapack(mcWorldPath)
+    for teleport in teleports:
+        with open(
+            f"{functionPath}/tp_{teleport['function']}.mcfunction",
+            "w",
+            encoding="utf-8",
+        ) as f:
+            f.write(f"tp @s {teleport['x']} {teleportY} {teleport['z']}\n")
+
+    with open(functionPath + "/warps.mcfunction", "w", encoding="utf-8") as f:
+        for teleport in teleports:
+            f.write(f"tellraw @s {json.dumps(warpEntry(teleport))}\n")
+    with open(functionPath + "/warp_book.mcfunction", "w", encoding="utf-8") as f:
+        f.write(warpBookCommand(teleports) + "\n")
+
+    print(
+        f"Wrote {len(teleports)} teleports, run /function arnis:warps "
+        + "or /function arnis:warp_book in the world"
+    )
@@ -0,0 +1,12 @@
+import pytest
+
+
+class FixedGeoreference:
+    # Pixels are the coordinates themselves
+    def toPixel(self, lat, lon):
+        return lat, lon
+
+
+@pytest.fixture
+def georeference():
+    return FixedGeoreference()
@@ -0,0 +1,23 @@
+import collections
+import pytest
+
+from src.alignment import alignedLength
+
+
+TestAlignmentParameters = collections.namedtuple(
+    "TestAlignmentParameters", ["length", "size", "mode", "result"]
+)
+
+
+@pytest.mark.parametrize(
+    "parameters",
+    (
+        TestAlignmentParameters(length=100, size=16, mode="outward", result=112),
+        TestAlignmentParameters(length=100, size=16, mode="inward", result=96),
+        TestAlignmentParameters(length=512, size=512, mode="outward", result=512),
+        TestAlignmentParameters(length=300, size=512, mode="inward", result=512),
+    ),
+)
+def test_aligned_length(parameters: TestAlignmentParameters):
+    result = alignedLength(parameters.length, parameters.size, parameters.mode)
+    assert result == parameters.result
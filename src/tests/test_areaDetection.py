@@ -0,0 +1,35 @@
+import collections
+import pytest
+
+from src.areaDetection import isArea
+
+
+TestAreaParameters = collections.namedtuple(
+    "TestAreaParameters", ["tags", "closed", "result"]
+)
+
+
+@pytest.mark.parametrize(
+    "parameters",
+    (
+        TestAreaParameters(tags={"building": "yes"}, closed=True, result=True),
+        TestAreaParameters(tags={"building": "yes"}, closed=False, result=False),
+        TestAreaParameters(tags={"landuse": "grass"}, closed=True, result=True),
+        TestAreaParameters(tags={"highway": "pedestrian"}, closed=True, result=False),
+        TestAreaParameters(
+            tags={"highway": "pedestrian", "area": "yes"}, closed=True, result=True
+        ),
+        TestAreaParameters(
+            tags={"leisure": "park", "area": "no"}, closed=True, result=False
+        ),
+        TestAreaParameters(tags={"man_made": "pier"}, closed=True, result=False),
+        TestAreaParameters(tags={"natural": "tree_row"}, closed=True, result=False),
+        TestAreaParameters(tags={"waterway": "river"}, closed=True, result=False),
+        TestAreaParameters(tags={"waterway": "riverbank"}, closed=True, result=True),
+    ),
+)
+def test_is_area(parameters: TestAreaParameters):
+    nodes = [[0, 0], [0, 5], [5, 5], [5, 0]]
+    if parameters.closed:
+        nodes.append(nodes[0])
+    assert isArea({"tags": parameters.tags, "nodes": nodes}) == parameters.result
@@ -0,0 +1,33 @@
+from argparse import Namespace
+
+import numpy as np
+import pytest
+
+from src import audit
+
+
+def test_outputs_are_written_by_the_first_run_only(monkeypatch):
+    outputs = []
+
+    def processData(data, args):
+        outputs.append((args.markers, args.teleports, args.exportRasters))
+        return np.zeros((2, 2, 1), np.uint8), None
+
+    class Exited(Exception):
+        pass
+
+    def exit(code):
+        raise Exited(code)
+
+    monkeypatch.setattr(audit, "processData", processData)
+    monkeypatch.setattr(audit.os, "_exit", exit)
+    args = Namespace(markers="dynmap", teleports=True, exportRasters="rasters")
+    with pytest.raises(Exited):
+        audit.verifyOrderIndependence({"elements": []}, args, runs=2)
+    assert outputs == [
+        ("dynmap", True, "rasters"),
+        (None, False, None),
+        (None, False, None),
+    ]
+    # The options of the main run are left alone
+    assert args.markers == "dynmap"
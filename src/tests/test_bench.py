@@ -0,0 +1,21 @@
+from src.bench import benchCommand, fixtureName, formatResults
+
+
+def test_fixture_name():
+    assert fixtureName("/data/bench/city.json") == "city"
+
+
+def test_bench_command_generates_the_fixture_into_a_new_world():
+    command = benchCommand("/data/city.json", "/tmp/world", "/tmp/metrics.json")
+    assert command[command.index("--offline-demo") + 1] == "/data/city.json"
+    assert command[command.index("--path") + 1] == "/tmp/world"
+    assert command[command.index("--metrics-out") + 1] == "/tmp/metrics.json"
+    assert "--new-world" in command
+
+
+def test_format_results():
+    results = {"demo": {"stages": {"fetch": 0.5, "generate": 2.0}}}
+    lines = formatResults(results).splitlines()
+    assert lines[0].startswith("demo: ")
+    assert lines[1].split()[0] == "fetch"
+    assert lines[2].split()[0] == "generate"
@@ -0,0 +1,14 @@
+from src.biomes import chunkBiomes
+
+
+def test_biomes_follow_the_landuse():
+    imgarray = [[32] * 16 for _ in range(4)] + [[38] * 16 for _ in range(12)]
+    biomes = chunkBiomes(imgarray, 0, 0)
+    assert len(biomes) == 1024
+    # First cell column (x 0-3) is forest, the others are river
+    assert biomes[:4] == [4, 7, 7, 7]
+    assert biomes[:16] * 64 == biomes
+
+
+def test_outside_of_the_area_is_plains():
+    assert set(chunkBiomes([[32]], 1, 1)) == {1}
@@ -0,0 +1,11 @@
+import anvil
+
+from src.blockRecorder import BlockRecorder
+
+
+def test_fill_is_clipped_to_the_recorded_area():
+    recorder = BlockRecorder(4, 4, 4)
+    recorder.fill(anvil.Block("minecraft", "stone"), -2, 0, 2, 1, 10, 3)
+    stone = recorder.palette["minecraft:stone"]
+    assert (recorder.blocks[:, 2:4, 0:2] == stone).all()
+    assert (recorder.blocks == stone).sum() == 4 * 2 * 2
@@ -0,0 +1,23 @@
+import collections
+import pytest
+
+from src.buildingAge import getAgeClass, historic, modern, old
+
+
+TestAgeParameters = collections.namedtuple("TestAgeParameters", ["tags", "result"])
+
+
+@pytest.mark.parametrize(
+    "parameters",
+    (
+        TestAgeParameters(tags={}, result=0),
+        TestAgeParameters(tags={"start_date": "1790"}, result=historic),
+        TestAgeParameters(tags={"start_date": "~1890-05-01"}, result=old),
+        TestAgeParameters(tags={"start_date": "1970"}, result=0),
+        TestAgeParameters(tags={"start_date": "2015"}, result=modern),
+        TestAgeParameters(tags={"start_date": "C19"}, result=0),
+        TestAgeParameters(tags={"building:age": "500"}, result=historic),
+    ),
+)
+def test_age_class(parameters: TestAgeParameters):
+    assert getAgeClass(parameters.tags) == parameters.result
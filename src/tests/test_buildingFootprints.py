@@ -0,0 +1,39 @@
+from src.buildingFootprints import containsPoint, mergeFootprints
+
+
+def test_contains_point():
+    ring = [(0, 0), (0, 10), (10, 10), (10, 0), (0, 0)]
+    assert containsPoint(ring, (5, 5))
+    assert not containsPoint(ring, (15, 5))
+
+
+def test_merge_footprints():
+    data = {
+        "elements": [
+            {"type": "node", "id": 1, "lat": 0.0, "lon": 0.0},
+            {"type": "node", "id": 2, "lat": 0.0, "lon": 1.0},
+            {"type": "node", "id": 3, "lat": 1.0, "lon": 1.0},
+            {"type": "node", "id": 4, "lat": 10.0, "lon": 10.0},
+            {
+                "type": "way",
+                "id": 5,
+                "nodes": [1, 2, 3, 1],
+                "tags": {"building": "yes"},
+            },
+        ]
+    }
+    footprints = [
+        {
+            "ring": [(0, 0), (0, 1), (1, 1), (1, 0), (0, 0)],
+            "height": 12,
+            "levels": None,
+        },
+        {
+            "ring": [(5, 5), (5, 6), (6, 6), (6, 5), (5, 5)],
+            "height": None,
+            "levels": 3,
+        },
+    ]
+    assert mergeFootprints(data, footprints) == (1, 1)
+    assert data["elements"][4]["tags"]["height"] == "12"
+    assert data["elements"][-1]["tags"]["building:levels"] == "3"
@@ -0,0 +1,73 @@
+import collections
+import pytest
+
+from src.buildingHeight import (
+    getBuildingHeight,
+    isCurtainWall,
+    loadBuildingStyle,
+    maxLevels,
+)
+
+
+TestBuildingHeightParameters = collections.namedtuple(
+    "TestBuildingHeightParameters", ["tags", "rescale", "result"]
+)
+
+
+@pytest.mark.parametrize(
+    "parameters",
+    (
+        TestBuildingHeightParameters(tags={}, rescale=False, result=1),
+        TestBuildingHeightParameters(tags={"height": "8"}, rescale=False, result=2),
+        TestBuildingHeightParameters(tags={"height": "15"}, rescale=False, result=3),
+        TestBuildingHeightParameters(
+            tags={"building:levels": "3"}, rescale=False, result=2
+        ),
+        TestBuildingHeightParameters(
+            tags={"building:levels": "10"}, rescale=False, result=9
+        ),
+        TestBuildingHeightParameters(
+            tags={"building:levels": "40"}, rescale=False, result=9
+        ),
+        TestBuildingHeightParameters(
+            tags={"building:levels": "3"}, rescale=True, result=2
+        ),
+        TestBuildingHeightParameters(
+            tags={"building:levels": "40"}, rescale=True, result=7
+        ),
+    ),
+)
+def test_building_height(parameters: TestBuildingHeightParameters):
+    element = {"id": 1, "tags": parameters.tags}
+    assert getBuildingHeight(element, parameters.rescale) == parameters.result
+
+
+def test_rescale_keeps_order():
+    heights = [
+        getBuildingHeight({"id": 1, "tags": {"building:levels": str(levels)}}, True)
+        for levels in range(1, 200)
+    ]
+    assert heights == sorted(heights)
+    assert max(heights) == maxLevels - 1
+
+
+def test_building_style_defaults():
+    europe = loadBuildingStyle("europe")
+
+    def height(tags):
+        return getBuildingHeight({"id": 1, "tags": tags}, False, europe)
+
+    assert height({"building": "house"}) == 1
+    assert height({"building": "apartments"}) == 4
+    assert height({"building": "apartments", "building:levels": "2"}) == 1
+    assert height({"building": "yes"}) == europe["levels"]["default"] - 1
+
+
+def test_curtain_wall_selection():
+    europe = loadBuildingStyle("europe")
+    office = {"id": 1, "tags": {"building": "office"}}
+    house = {"id": 1, "tags": {"building": "house"}}
+    assert isCurtainWall(office, 9, europe)
+    assert not isCurtainWall(office, 2, europe)
+    assert not isCurtainWall(house, 9, europe)
+    assert not isCurtainWall(office, 9, loadBuildingStyle("default"))
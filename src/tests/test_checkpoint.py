@@ -0,0 +1,36 @@
+from src.checkpoint import Checkpoint
+
+options = {"city": "Arnis", "seed": None, "spawn": (5, 5), "yes": False}
+
+
+def test_checkpoint_is_resumed(tmp_path):
+    directory = str(tmp_path / "checkpoint")
+    checkpoint = Checkpoint(directory, options)
+    assert checkpoint.loadRawData() is None
+    assert checkpoint.savedRegions() == []
+
+    checkpoint.saveRawData({"elements": []})
+    checkpoint.saveRegions(["r.0.0", "r.0.1"])
+    assert checkpoint.generationSeed(1234) == 1234
+
+    # Confirming the area again doesn't change the result
+    resumed = Checkpoint(directory, dict(options, yes=True))
+    assert resumed.loadRawData() == {"elements": []}
+    assert resumed.savedRegions() == ["r.0.0", "r.0.1"]
+    assert resumed.generationSeed(5678) == 1234
+
+    resumed.remove()
+    assert not (tmp_path / "checkpoint").exists()
+
+
+def test_checkpoint_is_discarded_when_the_options_change(tmp_path):
+    directory = str(tmp_path / "checkpoint")
+    checkpoint = Checkpoint(directory, options)
+    checkpoint.saveRawData({"elements": []})
+    checkpoint.saveRegions(["r.0.0"])
+    checkpoint.generationSeed(1234)
+
+    changed = Checkpoint(directory, dict(options, city="Kappeln"))
+    assert changed.loadRawData() is None
+    assert changed.savedRegions() == []
+    assert changed.generationSeed(5678) == 5678
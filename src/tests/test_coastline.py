@@ -0,0 +1,36 @@
+from src.coastline import getIslandPolygons, getOceanPolygons, joinSegments
+
+
+def test_segments_are_joined():
+    chains = joinSegments([[(2, 2), (3, 3)], [(0, 0), (1, 1), (2, 2)]])
+    assert chains == [[(0, 0), (1, 1), (2, 2), (3, 3)]]
+
+
+def test_water_is_right_of_the_coastline():
+    # Coastline running from west to east, so the sea is in the south
+    polygons = getOceanPolygons([[(5, 0), (5, 10)]], 11, 11)
+    assert polygons == [[(5, 0), (5, 10), (0, 10), (0, 0)]]
+
+    # Coastline running from east to west, so the sea is in the north
+    polygons = getOceanPolygons([[(5, 10), (5, 0)]], 11, 11)
+    assert polygons == [[(5, 10), (5, 0), (10, 0), (10, 10)]]
+
+
+def test_open_ends_are_extended_to_the_border():
+    polygons = getOceanPolygons([[(5, 2), (5, 8)]], 11, 11)
+    assert polygons[0][0] == (5, 0)
+    assert polygons[0][3] == (5, 10)
+
+
+def test_islands_are_surrounded_by_sea():
+    # Counterclockwise ring around land, with the sea on its right
+    island = [(4, 4), (4, 6), (6, 6), (6, 4), (4, 4)]
+    assert getIslandPolygons([island]) == [island]
+    assert getOceanPolygons([island], 11, 11) == [[(0, 0), (0, 10), (10, 10), (10, 0)]]
+
+
+def test_enclosed_sea_is_filled():
+    # Clockwise ring, so the land is outside of it
+    sea = [(4, 4), (6, 4), (6, 6), (4, 6), (4, 4)]
+    assert getIslandPolygons([sea]) == []
+    assert getOceanPolygons([sea], 11, 11) == [sea]
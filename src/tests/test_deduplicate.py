@@ -0,0 +1,31 @@
+from src.deduplicate import deduplicate, geometryKey
+
+
+def test_duplicate_ids_are_removed():
+    data = {
+        "elements": [
+            {"type": "node", "id": 1, "lat": 1, "lon": 1},
+            {"type": "node", "id": 1, "lat": 1, "lon": 1},
+            {"type": "way", "id": 1, "nodes": [1, 2]},
+        ]
+    }
+    assert deduplicate(data) == 1
+    assert len(data["elements"]) == 2
+
+
+def test_overlapping_ways_are_removed():
+    data = {
+        "elements": [
+            {"type": "way", "id": 1, "nodes": [1, 2, 3], "tags": {"highway": "a"}},
+            {"type": "way", "id": 2, "nodes": [3, 2, 1], "tags": {"highway": "a"}},
+            {"type": "way", "id": 3, "nodes": [1, 2, 3], "tags": {"highway": "b"}},
+        ]
+    }
+    assert deduplicate(data) == 1
+    assert [element["id"] for element in data["elements"]] == [1, 3]
+
+
+def test_geometry_key_compares_the_geometry_itself():
+    # Not a hash, so distinct ways never collide
+    way = {"type": "way", "id": 1, "nodes": [3, 2, 1], "tags": {"highway": "a"}}
+    assert geometryKey(way) == ((1, 2, 3), (("highway", "a"),))
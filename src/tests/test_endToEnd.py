@@ -0,0 +1,64 @@
+import os
+import sys
+import json
+import subprocess
+import anvil
+import pytest
+from nbt import nbt
+
+from src.verification import verifyRegion
+
+repositoryPath = os.path.dirname(os.path.dirname(os.path.dirname(__file__)))
+
+
+def createWorld(worldPath):
+    os.makedirs(worldPath / "region")
+    level = nbt.NBTFile()
+    level.name = ""
+    data = nbt.TAG_Compound(name="Data")
+    data.tags.append(nbt.TAG_String(name="LevelName", value="Demo"))
+    data.tags.append(nbt.TAG_Int(name="SpawnX", value=0))
+    data.tags.append(nbt.TAG_Int(name="SpawnZ", value=0))
+    level.tags.append(data)
+    level.write_file(str(worldPath / "level.dat"))
+
+
+def paletteBlocks(regionPath):
+    region = anvil.Region.from_file(str(regionPath))
+    blocks = set()
+    for chunkX in range(32):
+        for chunkZ in range(32):
+            chunk = region.chunk_data(chunkX, chunkZ)
+            if chunk is None:
+                continue
+            for section in chunk["Level"]["Sections"]:
+                if "Palette" in section:
+                    blocks.update(str(block["Name"]) for block in section["Palette"])
+    return blocks
+
+
+@pytest.mark.skipif(
+    os.environ.get("ARNIS_E2E") != "1", reason="set ARNIS_E2E=1 to generate a world"
+)
+def test_offline_demo_generates_a_world(tmp_path):
+    createWorld(tmp_path)
+    subprocess.run(
+        [sys.executable, "arnis.py", "--offline-demo", "--path", str(tmp_path)],
+        cwd=repositoryPath,
+        check=True,
+    )
+
+    regionPaths = list((tmp_path / "region").glob("*.mca"))
+    assert len(regionPaths) > 0
+    blocks = set()
+    for regionPath in regionPaths:
+        assert verifyRegion(str(regionPath)) == []
+        blocks |= paletteBlocks(regionPath)
+    # Buildings and streets of the demo area
+    assert "minecraft:white_stained_glass" in blocks
+    assert "minecraft:black_concrete" in blocks
+
+    level = nbt.NBTFile(str(tmp_path / "level.dat"))
+    assert str(level["Data"]["LevelName"]).startswith("Demo (")
+    with open(tmp_path / "metadata.json", "r", encoding="utf-8") as f:
+        assert json.load(f)["license"] == "ODbL-1.0"
@@ -0,0 +1,23 @@
+from src import fillBudget
+from src.fillBudget import FillBudget
+
+
+def test_scanline_misses_fall_back_to_the_floodfill(monkeypatch):
+    filledPixels = [12, 0, None]
+    floodFills = []
+
+    def scanlineFill(img, *args, **kwargs):
+        return filledPixels.pop(0)
+
+    def floodFill(img, *args, timeout, **kwargs):
+        floodFills.append(timeout)
+        return img
+
+    monkeypatch.setattr(fillBudget, "scanlineFill", scanlineFill)
+    monkeypatch.setattr(fillBudget, "floodFill", floodFill)
+    budget = FillBudget(3, totalSeconds=30)
+    for _ in range(3):
+        assert budget.fill("img", 0, 0, 30, [], 10, 10) == "img"
+    # Only the area the scanline fill couldn't fill is flooded, with the
+    # budget left for the two remaining areas
+    assert floodFills == [15]
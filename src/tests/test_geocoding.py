@@ -0,0 +1,19 @@
+import pytest
+
+from src import geocoding
+
+
+def test_confirm_area_without_terminal(monkeypatch):
+    def closedInput(prompt):
+        raise EOFError()
+
+    class Exited(Exception):
+        pass
+
+    def exit(code):
+        raise Exited()
+
+    monkeypatch.setattr("builtins.input", closedInput)
+    monkeypatch.setattr(geocoding.os, "_exit", exit)
+    with pytest.raises(Exited):
+        geocoding.confirmArea("Arnis", (54.62, 9.92, 54.64, 9.95))
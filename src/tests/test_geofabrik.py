@@ -0,0 +1,19 @@
+import os
+import time
+
+import pytest
+
+# Needs the optional osmium
+geofabrik = pytest.importorskip("src.geofabrik")
+
+
+def test_extracts_expire_like_the_overpass_cache(tmp_path):
+    extract = tmp_path / "schleswig-holstein-latest.osm.pbf"
+    assert not geofabrik.isFresh(str(extract), 24)
+    extract.write_bytes(b"")
+    assert geofabrik.isFresh(str(extract), 24)
+    # --no-cache
+    assert not geofabrik.isFresh(str(extract), 0)
+    dayAgo = time.time() - 25 * 3600
+    os.utime(extract, (dayAgo, dayAgo))
+    assert not geofabrik.isFresh(str(extract), 24)
@@ -0,0 +1,44 @@
+import collections
+import pytest
+
+from src.georeference import Georeference
+from src.landcover import tileOrigin
+
+
+TestPixelParameters = collections.namedtuple(
+    "TestPixelParameters", ["lat", "lon", "result"]
+)
+
+
+@pytest.mark.parametrize(
+    "parameters",
+    (
+        TestPixelParameters(lat=54.6290000, lon=9.9360000, result=(0, 0)),
+        TestPixelParameters(lat=54.6291000, lon=9.9360000, result=(10, 0)),
+        TestPixelParameters(lat=54.6292300, lon=9.9385600, result=(23, 256)),
+    ),
+)
+def test_to_pixel(parameters: TestPixelParameters):
+    georeference = Georeference((1e7, 1e7), (546280000, 99350000), (100, 100), 100)
+    assert georeference.toPixel(parameters.lat, parameters.lon) == parameters.result
+    x, y = parameters.result
+    assert georeference.latitude(x) == pytest.approx(parameters.lat, abs=1e-6)
+    assert georeference.longitude(y) == pytest.approx(parameters.lon, abs=1e-6)
+
+
+TestTileParameters = collections.namedtuple("TestTileParameters", ["path", "result"])
+
+
+@pytest.mark.parametrize(
+    "parameters",
+    (
+        TestTileParameters(
+            path="tiles/ESA_WorldCover_10m_2021_v200_N54E009_Map.tif", result=(57, 9)
+        ),
+        TestTileParameters(
+            path="ESA_WorldCover_10m_2021_v200_S03W060_Map.tif", result=(0, -60)
+        ),
+    ),
+)
+def test_tile_origin(parameters: TestTileParameters):
+    assert tileOrigin(parameters.path) == parameters.result
@@ -0,0 +1,42 @@
+import pytest
+
+from src import getData
+
+
+def test_parse_json_accepts_response_bytes():
+    content = '{"elements": [{"type": "node", "id": 1, "tags": {"name": "Köln"}}]}'
+    data = getData.parseJson(content.encode("utf-8"))
+    assert data["elements"][0]["tags"]["name"] == "Köln"
+
+
+def test_cache_round_trip(tmp_path):
+    defaultDirectory = getData.cacheDirectory
+    getData.cacheDirectory = str(tmp_path)
+    try:
+        data = {"elements": [{"type": "way", "id": 2, "nodes": [1, 2]}]}
+        getData.saveCache("[out:json];", data)
+        assert getData.loadCache("[out:json];", 24) == data
+        assert getData.loadCache("[out:json];way;", 24) is None
+    finally:
+        getData.cacheDirectory = defaultDirectory
+
+
+def test_no_backoff_after_the_last_attempt(monkeypatch):
+    sleeps = []
+
+    def failingRequest(url, query, proxies):
+        raise Exception("HTTP 504")
+
+    class Exited(Exception):
+        pass
+
+    def exit(code):
+        raise Exited()
+
+    monkeypatch.setattr(getData, "requestData", failingRequest)
+    monkeypatch.setattr(getData.time, "sleep", sleeps.append)
+    monkeypatch.setattr(getData.os, "_exit", exit)
+    with pytest.raises(Exited):
+        getData.fetchData("[out:json];", ["https://a", "https://b"], retries=1)
+    # Two attempts on two mirrors, without waiting after the last request
+    assert len(sleeps) == 3
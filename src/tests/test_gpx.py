@@ -0,0 +1,20 @@
+from src.gpx import loadGpx
+
+
+def test_load_gpx(tmp_path):
+    path = tmp_path / "track.gpx"
+    path.write_text(
+        '<gpx xmlns="http://www.topografix.com/GPX/1/1" version="1.1">'
+        '<wpt lat="54.63" lon="9.93"><name>Start</name></wpt>'
+        "<trk><trkseg>"
+        '<trkpt lat="54.63" lon="9.93"/><trkpt lat="54.631" lon="9.932"/>'
+        "</trkseg></trk>"
+        '<rte><rtept lat="54.62" lon="9.94"/><rtept lat="54.621" lon="9.941"/></rte>'
+        "</gpx>"
+    )
+    tracks, waypoints = loadGpx(path)
+    assert tracks == [
+        [(54.63, 9.93), (54.631, 9.932)],
+        [(54.62, 9.94), (54.621, 9.941)],
+    ]
+    assert waypoints == [(54.63, 9.93)]
@@ -0,0 +1,54 @@
+import pytest
+
+from src import hooks
+
+plugin = """
+from src.hooks import registerPostProcessingPass
+
+
+@registerPostProcessingPass
+def markCorner(editor):
+    editor.setBlock("beacon", 0, 5, 0)
+    # Outside of the area, dropped by the editor
+    editor.setBlock("beacon", 10, 5, 0)
+"""
+
+
+@pytest.fixture
+def pluginDirectory(tmp_path, monkeypatch):
+    monkeypatch.syspath_prepend(str(tmp_path))
+    monkeypatch.setattr(hooks, "postProcessingPasses", [])
+    return tmp_path
+
+
+def test_plugin_passes_get_the_editor(pluginDirectory):
+    (pluginDirectory / "cornerPlugin.py").write_text(plugin)
+    hooks.loadPlugins(["cornerPlugin"])
+    placed = []
+    hooks.runPostProcessingPasses(
+        lambda *block: placed.append(block), None, [[0, 0], [0, 0]]
+    )
+    assert placed == [("beacon", 0, 5, 0)]
+
+
+def test_broken_plugins_are_reported(pluginDirectory, monkeypatch, capsys):
+    class Exited(Exception):
+        pass
+
+    def exit(code):
+        raise Exited()
+
+    monkeypatch.setattr(hooks.os, "_exit", exit)
+    (pluginDirectory / "brokenPlugin.py").write_text("import notInstalled\n")
+    with pytest.raises(Exited):
+        hooks.loadPlugins(["brokenPlugin"])
+    assert "Could not load plugin brokenPlugin" in capsys.readouterr().out
+
+
+def test_failing_passes_are_reported(pluginDirectory, capsys):
+    @hooks.registerPostProcessingPass
+    def failingPass(editor):
+        raise RuntimeError("no blocks left")
+
+    hooks.runPostProcessingPasses(None, None, [[0]])
+    assert "failingPass failed: no blocks left" in capsys.readouterr().out
@@ -0,0 +1,17 @@
+from datetime import datetime, timezone
+
+from src.levelData import flatLayers, solarDayTime, spawnY
+
+
+def test_solar_day_time():
+    noonUtc = datetime(2022, 6, 21, 12, 0, tzinfo=timezone.utc)
+    assert solarDayTime(0, noonUtc) == 6000
+    # 6 hours ahead of Greenwich, so it's evening
+    assert solarDayTime(90, noonUtc) == 12000
+    assert solarDayTime(-90, noonUtc) == 0
+
+
+def test_flat_layers_meet_the_generated_ground():
+    # Worlds start at y=-64, the generated ground surface is at y=1
+    top = -64 + sum(height for _, height in flatLayers) - 1
+    assert top == spawnY - 1
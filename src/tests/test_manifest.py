@@ -0,0 +1,17 @@
+from argparse import Namespace
+
+from src.manifest import hashData, inputHashes
+
+
+def test_data_hash_ignores_key_order():
+    assert hashData({"a": 1, "b": [1, 2]}) == hashData({"b": [1, 2], "a": 1})
+    assert hashData({"a": 1}) != hashData({"a": 2})
+
+
+def test_only_existing_files_are_hashed(tmp_path):
+    boundary = tmp_path / "boundary.json"
+    boundary.write_text("[]")
+    args = Namespace(boundary=str(boundary), buildingStyle="europe", gpx=None)
+    hashes = inputHashes(args)
+    assert list(hashes) == [str(boundary)]
+    assert len(hashes[str(boundary)]) == 64
@@ -0,0 +1,21 @@
+from src.georeference import Georeference
+from src.markers import collectMarkers
+
+
+def test_named_nodes_are_pois():
+    # Pixels are the coordinates themselves
+    georeference = Georeference((1, 1), (0, 0), (0, 0), 1)
+    nodes = [
+        (7, {"name": "Bahnhof", "railway": "station"}, 3, 4),
+        (8, {"name": "Elsewhere", "place": "village"}, 30, 4),
+    ]
+    markers = collectMarkers({"elements": []}, nodes, georeference, 10, 10)
+    assert markers == [
+        {
+            "id": "arnis_node_7",
+            "name": "Bahnhof",
+            "category": "poi",
+            # The image is flipped on its x axis before generating the world
+            "points": [(4, 6)],
+        }
+    ]
@@ -0,0 +1,19 @@
+from src.memoryBudget import bytesPerChunk, estimateMemory
+
+
+def test_memory_grows_with_the_area():
+    assert estimateMemory(16, 16) == bytesPerChunk
+    assert estimateMemory(4096, 512) == 8 * estimateMemory(512, 512)
+
+
+def test_streaming_keeps_two_region_columns():
+    assert estimateMemory(4096, 512, streamSave=True) == estimateMemory(1024, 512)
+    assert estimateMemory(256, 512, streamSave=True) == estimateMemory(256, 512)
+
+
+def test_recorded_blocks_are_not_streamed():
+    recorded = 36 * 4096 * 512 * 2
+    assert (
+        estimateMemory(4096, 512, streamSave=True, recordedHeight=36)
+        == estimateMemory(1024, 512) + recorded
+    )
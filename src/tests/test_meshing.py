@@ -0,0 +1,22 @@
+import numpy as np
+
+from src.meshing import greedyQuads, meshQuads
+
+
+def test_equal_runs_are_merged():
+    layer = np.array([[1, 1, 0], [1, 1, 2], [0, 0, 2]])
+    assert sorted(greedyQuads(layer)) == [(0, 2, 0, 2, 1), (1, 3, 2, 3, 2)]
+
+
+def test_hidden_faces_are_skipped():
+    blocks = np.zeros((3, 3, 3), np.uint16)
+    blocks[1, 1, 1] = 1
+    assert len(meshQuads(blocks)[1]) == 6
+
+    # Two blocks of the same type share their quads
+    blocks[1, 1, 2] = 1
+    assert len(meshQuads(blocks)[1]) == 6
+
+    blocks[1, 1, 2] = 2
+    quads = meshQuads(blocks)
+    assert len(quads[1]) == 5 and len(quads[2]) == 5
@@ -0,0 +1,50 @@
+import pytest
+
+from src import metrics
+from src.metrics import MetricsRecorder, elementKind
+
+
+def test_element_kinds():
+    assert elementKind(10) == "roads and railways"
+    # Center lines and medians are part of the road
+    assert elementKind(96) == "roads and railways"
+    assert elementKind(97) == "roads and railways"
+    assert elementKind(63) == "buildings"
+    assert elementKind(99) == "other"
+
+
+def test_blocks_are_counted_per_kind():
+    metrics = MetricsRecorder()
+    metrics.startElement(62)
+    metrics.countBlock()
+    metrics.countBlock()
+    metrics.endElement()
+    metrics.countBlock()
+    metrics.startElement(0)
+    metrics.countBlock()
+    metrics.endElement()
+
+    elements = metrics.report()["elements"]
+    assert elements["buildings"]["blocks"] == 2
+    assert elements["ground"]["blocks"] == 1
+    assert elements["ground"]["pixels"] == 1
+
+
+def test_counters_and_stages_are_reported():
+    metrics = MetricsRecorder()
+    metrics.count("elements", 5)
+    report = metrics.report({"fetch": 1.5})
+    assert report["stages"] == {"fetch": 1.5}
+    assert report["counters"] == {"elements": 5}
+
+
+@pytest.mark.skipif(metrics.resource is None, reason="no resource module on Windows")
+def test_peak_memory_units(monkeypatch):
+    class Usage:
+        ru_maxrss = 2048
+
+    monkeypatch.setattr(metrics.resource, "getrusage", lambda who: Usage())
+    monkeypatch.setattr(metrics.sys, "platform", "linux")
+    assert metrics.peakMemory() == 2048 * 1024
+    monkeypatch.setattr(metrics.sys, "platform", "darwin")
+    assert metrics.peakMemory() == 2048
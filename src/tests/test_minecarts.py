@@ -0,0 +1,13 @@
+from src.minecarts import connectedLine, railShape
+
+
+def test_diagonal_steps_get_a_corner():
+    assert connectedLine([(0, 0), (1, 1), (2, 1)]) == [(0, 0), (1, 0), (1, 1), (2, 1)]
+
+
+def test_rail_shapes():
+    assert railShape(True, True, False, False) == "north_south"
+    assert railShape(False, False, True, True) == "east_west"
+    assert railShape(False, True, True, False) == "south_east"
+    assert railShape(True, False, False, True) == "north_west"
+    assert railShape(False, False, False, False) == "north_south"
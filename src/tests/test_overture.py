@@ -0,0 +1,24 @@
+import pytest
+
+# Needs the optional pyarrow
+overture = pytest.importorskip("src.overture")
+
+
+def test_places_become_named_nodes():
+    columns = ["id", "geometry", "names", "categories", "confidence"]
+    assert overture.detectTheme(columns) == "places"
+    row = {"names": {"primary": "Arnis"}, "categories": {"primary": "train_station"}}
+    assert overture.featureTags("places", row) == {
+        "name": "Arnis",
+        "railway": "station",
+    }
+    row = {"names": {"primary": "Fischhalle"}, "categories": {"primary": "restaurant"}}
+    assert overture.featureTags("places", row)["amenity"] == "restaurant"
+    # Unnamed places have nothing to show
+    assert overture.featureTags("places", {"categories": {"primary": "cafe"}}) is None
+
+    builder = overture.ElementBuilder()
+    builder.way([(54.6, 9.9), (54.7, 9.9)], {"highway": "residential"})
+    builder.place((54.6, 9.9), {"name": "Arnis", "railway": "station"})
+    ids = [element["id"] for element in builder.elements if element["type"] == "node"]
+    assert ids == [1, 2, 3]
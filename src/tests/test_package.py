@@ -0,0 +1,19 @@
+import zipfile
+from argparse import Namespace
+
+from src.package import packageWorld
+
+
+def test_world_folder_is_packaged(tmp_path):
+    world = tmp_path / "Arnis"
+    (world / "region").mkdir(parents=True)
+    (world / "region" / "r.0.0.mca").write_bytes(b"region")
+    (world / "session.lock").write_bytes(b"lock")
+    args = Namespace(city="Arnis", state=None, country=None, attribution="OSM")
+
+    with zipfile.ZipFile(packageWorld(str(world), args)) as archive:
+        assert sorted(archive.namelist()) == [
+            "Arnis/README.txt",
+            "Arnis/region/r.0.0.mca",
+        ]
+        assert "Area: Arnis" in archive.read("Arnis/README.txt").decode()
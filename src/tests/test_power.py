@@ -0,0 +1,41 @@
+import numpy as np
+
+from src.power import (
+    cableSag,
+    drawPowerLines,
+    minorPowerLineCode,
+    powerLineCode,
+    powerPoleCode,
+    powerTowerCode,
+)
+
+
+def test_cable_sags_towards_the_middle_of_a_span():
+    assert cableSag(0, powerLineCode) == 0
+    assert cableSag(6, powerLineCode) == 1
+    assert cableSag(40, powerLineCode) == 3
+    assert cableSag(40, minorPowerLineCode) == 1
+
+
+def test_power_line_between_towers(georeference):
+    img = np.zeros((5, 10, 1), np.uint8)
+    img[2][4] = 10  # Street
+    data = {
+        "elements": [
+            {"type": "way", "tags": {"power": "line"}, "nodes": [[1, 2], [8, 2]]}
+        ]
+    }
+    img = drawPowerLines(img, data, [], georeference)
+    assert img[2][1] == powerTowerCode
+    assert img[2][8] == powerTowerCode
+    assert img[2][3] == powerLineCode
+    # Roads keep their pixel, the cable isn't drawn there
+    assert img[2][4] == 10
+
+
+def test_mapped_pole_without_a_line(georeference):
+    img = np.zeros((5, 5, 1), np.uint8)
+    points = [({"power": "pole"}, 1, 3), ({"power": "substation"}, 1, 1)]
+    img = drawPowerLines(img, {"elements": []}, points, georeference)
+    assert img[3][1] == powerPoleCode
+    assert img[1][1] == 0
@@ -0,0 +1,60 @@
+from argparse import Namespace
+
+import numpy as np
+
+from src.processData import processData
+
+
+def defaultArgs(path):
+    return Namespace(
+        buildingHeightsSource=None,
+        boundary=None,
+        markers=None,
+        teleports=False,
+        path=str(path),
+        debug=False,
+        buildingStyle="default",
+        fillBudget=None,
+        fillAlgorithm="scanline",
+        rescaleHeights=False,
+        minecarts=False,
+        landcover=None,
+        ferries=None,
+        promenade=False,
+        imagery=None,
+        imageryZoom=18,
+        downloadWorkers=4,
+        gpx=None,
+        exportRasters=None,
+    )
+
+
+def node(id, lat, lon, tags=None):
+    element = {"type": "node", "id": id, "lat": lat, "lon": lon}
+    if tags is not None:
+        element["tags"] = tags
+    return element
+
+
+def test_negative_coordinates(tmp_path):
+    # Buenos Aires, south of the equator and west of Greenwich. The boulder
+    # has fewer decimals, so its coordinates have to be padded.
+    data = {
+        "elements": [
+            node(1, -34.6030001, -58.3810001),
+            node(2, -34.6030001, -58.3808001),
+            node(3, -34.6032001, -58.3808001),
+            node(4, -34.6032001, -58.3810001),
+            node(5, -34.6034, -58.3812, {"natural": "rock"}),
+            {
+                "type": "way",
+                "id": 10,
+                "nodes": [1, 2, 3, 4, 1],
+                "tags": {"building": "yes"},
+            },
+        ]
+    }
+    img, _ = processData(data, defaultArgs(tmp_path))
+    codes = img[:, :, 0]
+    assert np.count_nonzero((codes >= 50) & (codes <= 89)) > 0
+    assert np.count_nonzero(codes == 25) == 1
@@ -0,0 +1,26 @@
+from src.progress import Progress, StageTimer, formatDuration
+
+
+def test_durations_are_readable():
+    assert formatDuration(42.4) == "42s"
+    assert formatDuration(125) == "2m 05s"
+    assert formatDuration(3 * 3600 + 60) == "3h 01m"
+
+
+def test_progress_is_printed_every_ten_percent(capsys):
+    progress = Progress("Pixel", 20)
+    for done in range(1, 21):
+        progress.update(done)
+    lines = capsys.readouterr().out.splitlines()
+    assert len(lines) == 10
+    assert lines[0].startswith("Pixel 2/20 (10%)") and "left" in lines[0]
+    assert "left" not in lines[-1]
+
+
+def test_stages_follow_each_other():
+    stages = StageTimer()
+    stages.startStage("fetch")
+    stages.startStage("process")
+    stages.endStage()
+    assert list(stages.durations) == ["fetch", "process"]
+    assert stages.summary() == "fetch 0s, process 0s"
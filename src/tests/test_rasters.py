@@ -0,0 +1,14 @@
+import pytest
+
+from src.georeference import Georeference
+from src.rasters import worldFile
+
+
+def test_world_file_starts_in_the_north_west():
+    georeference = Georeference((1e7, 1e7), (546280000, 99350000), (100, 100), 100)
+    values = [float(line) for line in worldFile(georeference, 11).splitlines()]
+    assert values[0] == pytest.approx(1e-5)
+    assert values[1:3] == [0, 0]
+    assert values[3] == pytest.approx(-1e-5)
+    assert values[4] == pytest.approx(9.936)
+    assert values[5] == pytest.approx(54.6291)
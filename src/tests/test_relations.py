@@ -0,0 +1,99 @@
+from src.buildingFootprints import footprintIdOffset
+from src.relations import assembleMultipolygons, assembleRings
+from src.scanlineFill import rowCrossings
+
+
+def test_rings_are_joined_in_both_directions():
+    rings, unclosed = assembleRings([[1, 2, 3], [1, 4, 3]])
+    assert rings == [[1, 2, 3, 4, 1]] and unclosed == []
+
+    rings, unclosed = assembleRings([[1, 2, 3], [3, 4]])
+    assert rings == [] and unclosed != []
+
+
+def test_unsupported_relations_are_counted():
+    data = {
+        "elements": [
+            {"type": "way", "id": 1, "nodes": [1, 2, 3]},
+            {"type": "way", "id": 2, "nodes": [3, 4, 1]},
+            {
+                "type": "relation",
+                "id": 10,
+                "tags": {"type": "multipolygon", "landuse": "forest"},
+                "members": [
+                    {"type": "way", "ref": 1, "role": "outer"},
+                    {"type": "way", "ref": 2, "role": "outer"},
+                ],
+            },
+            {
+                "type": "relation",
+                "id": 11,
+                "tags": {"type": "multipolygon", "building": "yes"},
+                "members": [{"type": "way", "ref": 1, "role": "outer"}],
+            },
+            {"type": "relation", "id": 12, "tags": {"type": "route_master"}},
+            {"type": "relation", "id": 13, "tags": {"type": "site"}},
+        ]
+    }
+    added, skipped = assembleMultipolygons(data)
+    assert added == 1
+    assert data["elements"][-1]["nodes"] == [1, 2, 3, 4, 1]
+    assert data["elements"][-1]["tags"]["landuse"] == "forest"
+    assert skipped == {
+        "multipolygon (unclosed)": 1,
+        "route_master": 1,
+        "site": 1,
+    }
+
+
+def test_ring_ids_are_unique_and_below_the_footprints():
+    square = [{"type": "way", "id": 1, "nodes": [1, 2, 3, 4, 1]}]
+    relations = [
+        {
+            "type": "relation",
+            "id": relationId,
+            "tags": {"type": "multipolygon", "landuse": "forest"},
+            "members": [{"type": "way", "ref": 1, "role": "outer"}] * 120,
+        }
+        for relationId in (1, 2, 12345678901)
+    ]
+    data = {"elements": square + relations}
+    added, _ = assembleMultipolygons(data)
+    ids = [element["id"] for element in data["elements"][-added:]]
+    assert len(set(ids)) == added
+    assert max(ids) < footprintIdOffset - 10**9
+
+
+def test_holes_are_cut_out_of_the_outer_ring():
+    corners = {1: (0, 0), 2: (0, 9), 3: (9, 9), 4: (9, 0)}
+    corners.update({5: (3, 3), 6: (3, 6), 7: (6, 6), 8: (6, 3)})
+    data = {
+        "elements": [
+            {"type": "node", "id": node, "lat": lat, "lon": lon}
+            for node, (lat, lon) in corners.items()
+        ]
+        + [
+            {"type": "way", "id": 1, "nodes": [1, 2, 3, 4, 1]},
+            {"type": "way", "id": 2, "nodes": [5, 6, 7, 8, 5]},
+            {
+                "type": "relation",
+                "id": 10,
+                "tags": {"type": "multipolygon", "landuse": "forest"},
+                "members": [
+                    {"type": "way", "ref": 1, "role": "outer"},
+                    {"type": "way", "ref": 2, "role": "inner"},
+                ],
+            },
+        ]
+    }
+    added, _ = assembleMultipolygons(data)
+    assert added == 1
+    nodes = data["elements"][-1]["nodes"]
+    assert nodes == [1, 2, 3, 4, 1, 5, 6, 7, 8, 5, 1]
+    # The row through the hole is only filled up to it and from it on
+    polygon = [corners[node] for node in nodes]
+    crossings = rowCrossings(polygon, 4)
+    assert [(start, end) for start, end in zip(crossings[::2], crossings[1::2])] == [
+        (0, 3),
+        (6, 9),
+    ]
@@ -0,0 +1,52 @@
+import numpy as np
+
+from src.roadMarkings import (
+    centerLineCode,
+    isMarked,
+    laneDividerCode,
+    laneLines,
+    paintRoadMarkings,
+    vertexOffsets,
+)
+
+
+def test_marked_roads():
+    assert isMarked({"highway": "primary"})
+    assert isMarked({"highway": "residential", "lanes": "2"})
+    assert not isMarked({"highway": "residential"})
+    assert not isMarked({"highway": "primary", "lanes": "1"})
+
+
+def test_lane_lines():
+    assert laneLines({"highway": "primary"}) == [(0, centerLineCode, False)]
+    lines = laneLines({"highway": "primary", "lanes": "4"})
+    assert [code for _, code, _ in lines] == [
+        laneDividerCode,
+        centerLineCode,
+        laneDividerCode,
+    ]
+    oneway = laneLines({"highway": "primary", "lanes": "2", "oneway": "yes"})
+    assert oneway == [(0, laneDividerCode, True)]
+
+
+def test_offsets_keep_their_distance_at_bends():
+    offsets = vertexOffsets([[0, 0], [10, 0], [10, 10]])
+    assert offsets[0] == (0, -1)
+    # Diagonal at the corner, reaching the corner of both offset lines
+    assert round(offsets[1][0], 6) == 1
+    assert round(offsets[1][1], 6) == -1
+
+
+def test_center_line_follows_the_road():
+    img = np.zeros((5, 20, 1), np.uint8)
+    for x in range(20):
+        for y in range(5):
+            img[y][x] = 10
+    data = {
+        "elements": [
+            {"type": "way", "tags": {"highway": "primary"}, "nodes": [[0, 2], [19, 2]]}
+        ]
+    }
+    img = paintRoadMarkings(img, data)
+    assert all(img[2][x] == centerLineCode for x in range(20))
+    assert img[1][5] == 10
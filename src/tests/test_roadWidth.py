@@ -0,0 +1,30 @@
+from src.roadWidth import hasMedian, maxBlockRange, parseWidth, roadBlockRange
+
+
+def test_parse_width():
+    assert parseWidth("7") == 7
+    assert parseWidth("7.5 m") == 7.5
+    assert parseWidth("7,5") == 7.5
+    assert parseWidth("12'") is None
+    assert parseWidth(None) is None
+
+
+def test_width_from_lanes_and_class():
+    # 2 * range + 1 blocks wide
+    assert roadBlockRange({"highway": "residential"}) == 2
+    assert roadBlockRange({"highway": "motorway"}) == 4
+    assert roadBlockRange({"highway": "service"}) == 1
+    assert roadBlockRange({"highway": "primary", "lanes": "3"}) == 4
+    assert roadBlockRange({"highway": "primary", "lanes": "2;3"}) == 4
+
+
+def test_width_tag_takes_precedence():
+    assert roadBlockRange({"highway": "primary", "lanes": "4", "width": "5"}) == 2
+    assert roadBlockRange({"highway": "primary", "width": "500"}) == maxBlockRange
+
+
+def test_median_of_dual_carriageways():
+    assert hasMedian({"highway": "primary", "lanes": "4"})
+    assert not hasMedian({"highway": "primary", "lanes": "4", "oneway": "yes"})
+    assert not hasMedian({"highway": "primary", "lanes": "2"})
+    assert hasMedian({"highway": "primary", "dual_carriageway": "yes"})
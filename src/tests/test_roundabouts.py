@@ -0,0 +1,34 @@
+import numpy as np
+
+from src.roundabouts import (
+    drawRoundabout,
+    isRoundabout,
+    islandCode,
+    roundaboutCircle,
+)
+
+# Jagged octagon around (10, 10) like a mapped roundabout
+octagon = [[16, 10], [14, 14], [10, 16], [6, 14], [4, 10], [6, 6], [10, 4], [14, 6]]
+octagon.append(octagon[0])
+
+
+def test_only_closed_roundabouts():
+    assert isRoundabout({"tags": {"junction": "roundabout"}, "nodes": octagon})
+    assert not isRoundabout({"tags": {"junction": "roundabout"}, "nodes": octagon[:4]})
+    assert not isRoundabout({"tags": {"highway": "residential"}, "nodes": octagon})
+
+
+def test_circle_through_the_nodes():
+    centerX, centerY, radius = roundaboutCircle(octagon)
+    assert (centerX, centerY) == (10, 10)
+    assert 5 < radius < 6
+
+
+def test_carriageway_around_an_island():
+    img = np.zeros((21, 21, 1), np.uint8)
+    img = drawRoundabout(img, octagon, 1)
+    assert img[10][10] == islandCode
+    # The carriageway is round, also between the nodes
+    assert img[10][16] == 10
+    assert img[14][14] == 10
+    assert img[10][18] == 0
@@ -0,0 +1,29 @@
+import numpy as np
+
+from src.scanlineFill import rowCrossings, scanlineFill
+
+
+def test_crossings_of_a_square():
+    square = [(1, 1), (5, 1), (5, 5), (1, 5)]
+    assert rowCrossings(square, 3) == [1, 5]
+    assert rowCrossings(square, 5) == []
+
+
+def test_self_intersecting_outline_does_not_leak():
+    img = np.zeros((10, 10, 1), np.uint8)
+    # Bow tie, the first vertex is dropped like the outlines built in processData
+    bowTie = np.array([(0, 0), (1, 1), (7, 7), (7, 1), (1, 7), (1, 1)])
+    assert scanlineFill(img, 4, 2, 30, bowTie, 10, 10) > 0
+    # Only the left and right triangles are inside
+    assert img[4][2][0] == 30 and img[4][6][0] == 30
+    assert img[2][4][0] == 0
+    assert img[0][0][0] == 0 and img[9][9][0] == 0
+
+
+def test_buildings_are_built_over_roads():
+    img = np.zeros((10, 10, 1), np.uint8)
+    img[3, :, 0] = 10
+    square = np.array([(0, 0), (1, 1), (6, 1), (6, 6), (1, 6), (1, 1)])
+    scanlineFill(img, 4, 4, 70, square, 10, 10, elementType="building")
+    assert img[3][3][0] == 70
+    assert img[3][8][0] == 10
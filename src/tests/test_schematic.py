@@ -0,0 +1,8 @@
+import numpy as np
+
+from src.schematic import encodeVarints
+
+
+def test_large_palette_indices_take_two_bytes():
+    data = encodeVarints(np.array([1, 200, 127, 128], np.uint16))
+    assert list(data) == [1, 200, 1, 127, 128, 1]
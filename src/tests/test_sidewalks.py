@@ -0,0 +1,58 @@
+import numpy as np
+
+from src.sidewalks import (
+    crossingCode,
+    drawSidewalks,
+    leftNormal,
+    paintCrossings,
+    sidewalkCode,
+    sidewalkSides,
+)
+
+
+def test_sidewalk_sides():
+    assert sidewalkSides({"highway": "service", "sidewalk": "both"}) == {
+        "left",
+        "right",
+    }
+    assert sidewalkSides({"highway": "residential", "sidewalk": "right"}) == {
+        "right"
+    }
+    assert sidewalkSides({"highway": "residential", "sidewalk": "separate"}) == set()
+    assert sidewalkSides({"highway": "service", "sidewalk:left": "yes"}) == {"left"}
+    # Implied by the road class
+    assert sidewalkSides({"highway": "residential"}) == {"left", "right"}
+    assert sidewalkSides({"highway": "service"}) == set()
+
+
+def test_left_of_a_way_to_the_north_is_west():
+    assert leftNormal([0, 5], [4, 5]) == (0, -1)
+
+
+def residentialRoad(tags):
+    # A road to the north along x, 2 blocks to each side of y=7
+    img = np.zeros((15, 20, 1), np.uint8)
+    for x in range(20):
+        for y in range(5, 10):
+            img[y][x] = 10
+    tags = dict(tags, highway="residential")
+    data = {"elements": [{"type": "way", "tags": tags, "nodes": [[0, 7], [19, 7]]}]}
+    return img, data
+
+
+def test_sidewalk_on_one_side():
+    img, data = residentialRoad({"sidewalk": "left"})
+    img = drawSidewalks(img, data)
+    assert img[4][10] == sidewalkCode
+    assert img[3][10] == sidewalkCode
+    assert img[2][10] == 0
+    assert img[10][10] == 0
+
+
+def test_zebra_crossing_across_the_road(georeference):
+    img, data = residentialRoad({"sidewalk": "no"})
+    points = [({"highway": "crossing"}, 10, 7)]
+    img = paintCrossings(img, data, points, georeference)
+    assert [int(img[y][10]) for y in range(5, 10)] == [19, 10, 19, 10, 19]
+    assert img[7][9] == crossingCode
+    assert img[7][12] == 10
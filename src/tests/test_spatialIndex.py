@@ -0,0 +1,11 @@
+from src.spatialIndex import GridIndex
+
+
+def test_only_intersecting_boxes_are_found():
+    index = GridIndex(1)
+    index.insert("small", (0.2, 0.2, 0.4, 0.4))
+    index.insert("large", (0, 0, 5, 5))
+    index.insert("far", (10, 10, 11, 11))
+    assert sorted(index.query((0.3, 0.3, 0.5, 0.5))) == ["large", "small"]
+    assert index.query((3, 3, 3, 3)) == ["large"]
+    assert index.query((-3, -3, -2, -2)) == []
@@ -0,0 +1,59 @@
+import numpy as np
+
+from src.roadWidth import roadBlockRange
+from src.streetLamps import lampSpacing, placeStreetLamps, streetLampCode
+
+
+def residentialRoad(img, length, tags=None):
+    # Drawn around the center line in the middle of the image, with a 2 block
+    # sidewalk on each side
+    tags = dict(tags or {}, highway="residential")
+    center = img.shape[0] // 2
+    blockRange = roadBlockRange(tags)
+    for x in range(length):
+        for y in range(center - blockRange - 2, center + blockRange + 3):
+            img[y][x] = 10 if abs(y - center) <= blockRange else 11
+    return {
+        "elements": [
+            {
+                "type": "way",
+                "tags": tags,
+                "nodes": [[0, center], [length - 1, center]],
+            }
+        ]
+    }
+
+
+def lamps(img):
+    return [
+        (x, y)
+        for y in range(img.shape[0])
+        for x in range(img.shape[1])
+        if img[y][x] == streetLampCode
+    ]
+
+
+def test_mapped_lamp_moves_off_the_road(georeference):
+    img = np.zeros((11, 20, 1), np.uint8)
+    data = residentialRoad(img, 20)
+    points = [({"highway": "street_lamp"}, 4, 6)]
+    img = placeStreetLamps(img, data, points, georeference)
+    assert lamps(img) == [(4, 8)]
+
+
+def test_lamps_along_residential_roads_without_mapped_lamps(georeference):
+    img = np.zeros((11, lampSpacing * 4, 1), np.uint8)
+    data = residentialRoad(img, lampSpacing * 4)
+    img = placeStreetLamps(img, data, [], georeference)
+    placed = lamps(img)
+    assert len(placed) == 4
+    # Alternating between both sides of the road
+    assert {y for _, y in placed} == {0, 10}
+
+
+def test_lamps_stay_behind_the_sidewalk_of_wide_roads(georeference):
+    img = np.zeros((31, lampSpacing * 2, 1), np.uint8)
+    data = residentialRoad(img, lampSpacing * 2, {"lanes": "4"})
+    img = placeStreetLamps(img, data, [], georeference)
+    # 6 blocks of road and 2 of sidewalk to each side of y=15
+    assert {y for _, y in lamps(img)} == {6, 24}
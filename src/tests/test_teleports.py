@@ -0,0 +1,30 @@
+from src.georeference import Georeference
+from src.teleports import collectTeleports, functionName, isPoi
+
+
+def test_function_names_are_unique():
+    used = set()
+    assert functionName("Bahnhof Arnis", 1, used) == "bahnhof_arnis"
+    assert functionName("Bahnhof Arnis", 2, used) == "bahnhof_arnis_2"
+    assert functionName("Schloß Arnis", 3, used) == "schlo_arnis"
+    assert functionName("東京駅", 4, used) == "4"
+
+
+def test_only_named_places_are_pois():
+    assert isPoi({"name": "Arnis", "railway": "station"})
+    assert isPoi({"name": "Kirche", "amenity": "place_of_worship"})
+    assert isPoi({"name": "Arnis", "place": "town"})
+    assert not isPoi({"name": "Hauptstraße", "highway": "residential"})
+    assert not isPoi({"amenity": "bench"})
+
+
+def test_stations_mapped_as_nodes():
+    # Pixels are the coordinates themselves
+    georeference = Georeference((1, 1), (0, 0), (0, 0), 1)
+    nodes = [
+        (5, {"name": "Arnis", "railway": "halt"}, 3, 4),
+        (6, {"name": "Bank", "leisure": "picnic_table"}, 40, 4),
+        (7, {"name": "Hauptstraße", "highway": "bus_stop"}, 3, 4),
+    ]
+    teleports = collectTeleports({"elements": []}, nodes, georeference, 10, 10)
+    assert teleports == [{"name": "Arnis", "function": "arnis", "x": 4, "z": 6}]
@@ -0,0 +1,22 @@
+import json
+
+from src.tiles import regionTile, splitIntoTiles
+
+
+def test_regions_are_grouped_into_tiles():
+    assert regionTile("r.0.0.mca", 2) == (0, 0)
+    assert regionTile("r.3.1.mca", 2) == (1, 0)
+
+
+def test_tiles_keep_their_regions(tmp_path):
+    world = tmp_path / "city"
+    (world / "region").mkdir(parents=True)
+    for name in ("r.0.0.mca", "r.1.0.mca", "r.2.2.mca"):
+        (world / "region" / name).write_bytes(b"")
+    (world / "metadata.json").write_text("{}")
+
+    paths = splitIntoTiles(str(world), 1, 1024, 600)
+    assert paths == [str(world) + "-0-0", str(world) + "-1-0"]
+    assert (tmp_path / "city-1-0" / "region" / "r.1.0.mca").exists()
+    metadata = json.loads((tmp_path / "city-1-0" / "metadata.json").read_text())
+    assert metadata["tile"] == {"x": 1, "z": 0, "minX": 512, "minZ": 0, "size": 512}
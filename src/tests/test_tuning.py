@@ -0,0 +1,13 @@
+from src.tuning import defaultDownloadWorkers, downloadWorkers, memoryBudget
+
+
+def test_download_workers_follow_the_cores():
+    assert downloadWorkers(None) == defaultDownloadWorkers
+    assert downloadWorkers(1) == 2
+    assert downloadWorkers(4) == 8
+    assert downloadWorkers(64) == 16
+
+
+def test_memory_budget_leaves_room():
+    assert memoryBudget(None) is None
+    assert memoryBudget(10 * 1024**3) == 7
@@ -0,0 +1,64 @@
+import os
+import json
+import shutil
+
+from .levelData import setLevelNameSuffix, setSpawn
+
+regionSize = 512
+
+
+def regionTile(fileName, regionsPerTile):
+    # r.<x>.<z>.mca
+    _, regionX, regionZ, _ = fileName.split(".")
+    return (int(regionX) // regionsPerTile, int(regionZ) // regionsPerTile)
+
+
+def tileBounds(tile, regionsPerTile):
+    size = regionsPerTile * regionSize
+    return {"minX": tile[0] * size, "minZ": tile[1] * size, "size": size}
+
+
+def splitIntoTiles(mcWorldPath, regionsPerTile, sizeX, sizeZ):
+    # Every tile becomes a copy of the finished world with only its own region
+    # files. Block coordinates stay the same in all tiles, so the worlds of a
+    # server network line up without any offsets.
+    regionPath = mcWorldPath + "/region"
+    tiles = {}
+    for fileName in sorted(os.listdir(regionPath)):
+        if fileName.endswith(".mca"):
+            tile = regionTile(fileName, regionsPerTile)
+            tiles.setdefault(tile, []).append(fileName)
+
+    tilePaths = []
+    for tile, fileNames in sorted(tiles.items()):
+        bounds = tileBounds(tile, regionsPerTile)
+        if bounds["minX"] >= sizeX or bounds["minZ"] >= sizeZ:
+            continue
+        tilePath = f"{mcWorldPath}-{tile[0]}-{tile[1]}"
+        shutil.copytree(
+            mcWorldPath,
+            tilePath,
+            ignore=shutil.ignore_patterns("region", "session.lock"),
+            dirs_exist_ok=True,
+        )
+        os.makedirs(tilePath + "/region", exist_ok=True)
+        for fileName in fileNames:
+            shutil.move(regionPath + "/" + fileName, tilePath + "/region/" + fileName)
+
+        metadataPath = tilePath + "/metadata.json"
+        if os.path.exists(metadataPath):
+            with open(metadataPath, "r", encoding="utf-8") as f:
+                metadata = json.load(f)
+            metadata["tile"] = {"x": tile[0], "z": tile[1], **bounds}
+            with open(metadataPath, "w", encoding="utf-8") as f:
+                json.dump(metadata, f, indent=2, ensure_ascii=False)
+
+        setLevelNameSuffix(tilePath, f"[{tile[0]}, {tile[1]}]")
+        setSpawn(
+            tilePath,
+            min(bounds["minX"] + bounds["size"] // 2, sizeX - 1),
+            min(bounds["minZ"] + bounds["size"] // 2, sizeZ - 1),
+        )
+        tilePaths.append(tilePath)
+        print(f"Wrote tile {tile[0]}, {tile[1]} to {tilePath}")
+    return tilePaths
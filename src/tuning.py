@@ -0,0 +1,26 @@
+import os
+
+defaultDownloadWorkers = 4
+# Share of the free memory the generation may use, the estimate is rough
+memoryShare = 0.7
+
+
+def availableMemory():
+    # Free physical memory in bytes, None where it can't be determined
+    try:
+        return os.sysconf("SC_AVPHYS_PAGES") * os.sysconf("SC_PAGE_SIZE")
+    except (ValueError, OSError, AttributeError):
+        return None
+
+
+def downloadWorkers(cores):
+    # Downloads wait for the network, so a few more than cores are fine
+    if cores is None:
+        return defaultDownloadWorkers
+    return max(2, min(2 * cores, 16))
+
+
+def memoryBudget(freeMemory):
+    if freeMemory is None:
+        return None
+    return round(freeMemory * memoryShare / 1024**3, 1)
@@ -0,0 +1,18 @@
+import anvil
+
+
+def verifyRegion(path):
+    # Returns the chunks which can't be read back, None for an unreadable file
+    try:
+        region = anvil.Region.from_file(path)
+    except Exception:
+        return None
+
+    failed = []
+    for chunkX in range(32):
+        for chunkZ in range(32):
+            try:
+                region.chunk_data(chunkX, chunkZ)
+            except Exception:
+                failed.append((chunkX, chunkZ))
+    return failed
@@ -0,0 +1,20 @@
+viewpointCode = 27
+
+
+def isLookoutGround(value):
+    # Not outside of the boundary, in water or in a building
+    return value not in (1, 37, 38, 40) and not 50 <= value <= 89
+
+
+def placeViewpoints(img, points, georeference):
+    for tags, lat, lon in points:
+        if tags.get("tourism") != "viewpoint":
+            continue
+        x, y = georeference.toPixel(lat, lon)
+        if (
+            0 <= y < img.shape[0]
+            and 0 <= x < img.shape[1]
+            and isLookoutGround(img[y][x])
+        ):
+            img[y][x] = viewpointCode
+    return img